@@ -0,0 +1,55 @@
+//! Compares the [`StackLike`] backends of [`Stack`].
+#![feature(test)]
+extern crate test;
+
+use dsrs::lists::LinkedList;
+use dsrs::stack::{Stack, StackLike};
+
+use test::{black_box, Bencher};
+
+const COUNT: u64 = 10_000;
+
+fn push_then_pop<S: StackLike<u64>>(bench: &mut Bencher) {
+    bench.iter(|| {
+        let mut stack = Stack::<u64, S>::new();
+        for value in 0..COUNT {
+            stack.push(black_box(value)).unwrap()
+        }
+        while let Some(value) = stack.pop() {
+            black_box(value);
+        }
+    })
+}
+
+fn interleaved<S: StackLike<u64>>(bench: &mut Bencher) {
+    bench.iter(|| {
+        let mut stack = Stack::<u64, S>::new();
+        for value in 0..COUNT {
+            stack.push(black_box(value)).unwrap();
+            if value % 3 == 0 {
+                black_box(stack.pop());
+            }
+        }
+        black_box(stack.clear())
+    })
+}
+
+#[bench]
+fn vec_push_then_pop(bench: &mut Bencher) {
+    push_then_pop::<Vec<_>>(bench)
+}
+
+#[bench]
+fn list_push_then_pop(bench: &mut Bencher) {
+    push_then_pop::<LinkedList<_>>(bench)
+}
+
+#[bench]
+fn vec_interleaved(bench: &mut Bencher) {
+    interleaved::<Vec<_>>(bench)
+}
+
+#[bench]
+fn list_interleaved(bench: &mut Bencher) {
+    interleaved::<LinkedList<_>>(bench)
+}
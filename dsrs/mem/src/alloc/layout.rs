@@ -12,7 +12,7 @@ pub type Result<T> = std::result::Result<T, LayoutErr>;
 /// Get `(size, align)` for [`Sized`] types.
 #[allow(clippy::inline_always)]
 #[must_use]
-#[inline(always)] // used only once
+#[inline(always)] // trivial wrapper
 const fn size_align<T>() -> (usize, usize) {
     (size_of::<T>(), align_of::<T>())
 }
@@ -210,6 +210,34 @@ impl Layout {
         unsafe { Self::from_size_align_unchecked(size, align) }
     }
 
+    /// Creates a layout describing the record for a `[T; n]`.
+    ///
+    /// See [`std::alloc::Layout::array`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mem::alloc::Layout;
+    ///
+    /// assert_eq!(Layout::array::<u64>(3).unwrap(), Layout::new::<[u64; 3]>());
+    /// assert!(Layout::array::<u64>(usize::MAX).is_err())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// On arithmetic overflow, or if the total size would overflow
+    /// when padding.
+    #[inline]
+    pub const fn array<T>(n: usize) -> Result<Self> {
+        let (size, align) = size_align::<T>();
+        // sizes are always a multiple of the alignment, so
+        // there is no padding between elements
+        match size.checked_mul(n) {
+            Some(size) => Self::from_size_align(size, align),
+            None => Err(LAYOUT_ERR),
+        }
+    }
+
     /// Produces layout describing a record that could be used to
     /// allocate backing structure for `T` (which could be a trait
     /// or other unsized type like a slice).
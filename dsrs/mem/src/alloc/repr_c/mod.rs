@@ -6,7 +6,7 @@ pub use field_tuple::FieldTuple;
 use self::field_tuple::layout_with_last_field;
 use super::layout::{Layout, Result};
 use super::{grow, shrink, NonNull};
-use std::alloc::handle_alloc_error;
+use std::alloc::{handle_alloc_error, AllocRef, Global};
 
 /// The starting fields of a `#[repr(C)]` struct `T`.
 ///
@@ -335,4 +335,159 @@ pub unsafe trait ReprC {
         debug_assert!(last_layout == Layout::for_value(last.as_ref()));
         (last, start)
     }
+
+    /// Expands a box with the last field into the full struct, allocated
+    /// with `alloc`.
+    ///
+    /// This works just like [`expand`](ReprC::expand), but the memory of
+    /// the boxed field can't be reused, since it belongs to the global
+    /// allocator. So the struct is allocated with `alloc` and the last
+    /// field is moved into it, before releasing the box memory.
+    ///
+    /// The returned pointer is owned by the caller, and should be
+    /// eventually released with [`split_in`](ReprC::split_in), or by
+    /// dropping it in place and deallocating with the same allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    /// use mem::alloc::ReprC;
+    /// use std::alloc::System;
+    ///
+    /// #[repr(C)]
+    /// struct Unique<T: ?Sized> {
+    ///     id: usize,
+    ///     data: T
+    /// }
+    ///
+    /// // SAFETY: Unique<T> is a repr(C) struct
+    /// unsafe impl<T: ?Sized> ReprC for Unique<T> {
+    ///     type Fields = (usize, T);
+    /// }
+    ///
+    /// let name = String::from("Hannah Montana");
+    /// let unique = Unique::expand_in(name.clone().into_boxed_str(), (12,), &mut System);
+    ///
+    /// // SAFETY: `unique` was just built and is still valid
+    /// assert_eq!(unsafe { &unique.as_ref().data }, name);
+    ///
+    /// // SAFETY: `unique` was allocated with `System`
+    /// let (data, (id,)) = unsafe { Unique::split_in(unique, &mut System) };
+    /// assert_eq!((String::from(data), id), (name, 12))
+    /// ```
+    #[must_use]
+    #[inline]
+    fn expand_in<A: AllocRef>(
+        last: Box<Last<Self>>,
+        fields: Start<Self>,
+        alloc: &mut A,
+    ) -> NonNull<Self> {
+        let last_ptr = Box::into_raw(last);
+
+        // SAFETY: since `last` is a reference, it is a valid pointer to `Last`
+        let (self_layout, last_layout) =
+            match unsafe { layout_with_last_field::<Self::Fields>(last_ptr) } {
+                // SAFETY: again, valid reference
+                Err(_) => handle_alloc_error(unsafe { Layout::for_value_raw(last_ptr).inner() }),
+                Ok((layout, _, last)) => (layout, last),
+            };
+
+        let memory = match alloc.alloc(self_layout.inner()) {
+            Err(_) => handle_alloc_error(self_layout.inner()),
+            Ok(ptr) => NonNull(ptr.cast()),
+        };
+        // SAFETY: pointer can hold a Self, but its uninitialized, the metadata
+        // must also be the same, as Last is its last field
+        let self_ptr =
+            unsafe { NonNull::new_unchecked(last_ptr).update(memory).cast_unsized::<Self>() };
+
+        // SAFETY: self_ptr can hold Self, which is a repr(C) with Self::Fields
+        // also `last` is valid since it was a reference
+        unsafe {
+            // move the last field to the end
+            Self::Fields::write_last(self_ptr.as_ptr() as *mut u8, last_ptr);
+            // insert starting fields
+            Self::Fields::write_start(self_ptr.as_ptr() as *mut u8, fields);
+        }
+        if last_layout.size() != 0 {
+            // SAFETY: a box must always be allocated via the global allocator and
+            // its content was moved, so it shouldn't be dropped
+            unsafe {
+                let last_ptr = NonNull::new_unchecked(last_ptr).cast().inner();
+                Global.dealloc(last_ptr, last_layout.inner())
+            }
+        }
+
+        // check validity of layouts
+        // SAFETY: self_ptr is now valid and initialized
+        debug_assert!(self_layout == Layout::for_value(unsafe { self_ptr.as_ref() }));
+        self_ptr
+    }
+
+    /// Splits a `Self` allocated with `alloc` into its first fields and
+    /// the boxed last field.
+    ///
+    /// This is the inverse of [`expand_in`](ReprC::expand_in). The last
+    /// field is moved to a new box, from the global allocator, and the
+    /// memory of `this` is released with `alloc`.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to a valid and initialized `Self`, which was
+    /// allocated with `alloc`. After this call, `this` is dangling.
+    ///
+    /// # Example
+    ///
+    /// See [`expand_in`](ReprC::expand_in).
+    #[must_use]
+    #[inline]
+    unsafe fn split_in<A: AllocRef>(
+        this: NonNull<Self>,
+        alloc: &mut A,
+    ) -> (Box<Last<Self>>, Start<Self>) {
+        // SAFETY: caller guarantees that `this` is valid
+        let self_layout = Layout::for_value(unsafe { this.as_ref() });
+        let self_ptr = this.as_ptr();
+        // SAFETY: since self is initialized, it can hold its Last field
+        // but the pointer might be to another field
+        let last_meta = unsafe { this.cast_unsized::<Last<Self>>() };
+
+        // SAFETY: NOT SURE: Self can hold Last<Self>, the metadata is correct and,
+        // since Self is a #[repr(C)] struct, the pointer is aligned correctly,
+        // but it might not point to any valid Last object
+        let last_layout = unsafe { layout_with_last_field::<Self::Fields>(last_meta.as_ptr()) };
+        let last_layout = match last_layout {
+            Err(_) => handle_alloc_error(self_layout.inner()),
+            Ok((this_layout, _, layout)) => {
+                // check validity of layouts
+                debug_assert!(this_layout == self_layout);
+                layout
+            },
+        };
+
+        let memory = if last_layout.size() == 0 {
+            last_layout.dangling()
+        } else {
+            match Global.alloc(last_layout.inner()) {
+                Err(_) => handle_alloc_error(last_layout.inner()),
+                Ok(ptr) => NonNull(ptr.cast()),
+            }
+        };
+        let last_ptr = last_meta.update(memory).as_ptr();
+
+        // SAFETY: `this` points to an initialized object of Self, so the fields
+        // are safe to read, and `last_ptr` was just allocated for `Last<Self>`
+        let start = unsafe {
+            Self::Fields::read_last(self_ptr as *const u8, last_ptr);
+            Self::Fields::read_start(self_ptr as *const u8)
+        };
+        // SAFETY: caller guarantees that `this` was allocated with `alloc`, and
+        // all of its fields were moved out
+        unsafe { alloc.dealloc(this.cast().inner(), self_layout.inner()) };
+
+        // SAFETY: last_ptr is a valid global allocation for `Last<Self>`
+        let last = unsafe { Box::from_raw(last_ptr) };
+        (last, start)
+    }
 }
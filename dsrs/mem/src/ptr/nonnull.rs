@@ -194,6 +194,28 @@ impl<T: ?Sized> NonNull<T> {
         self.0
     }
 
+    /// Wrap a [`std::ptr::NonNull`] into a `NonNull`.
+    ///
+    /// This is the inverse of [`inner`](NonNull::inner), useful for
+    /// pointers returned by allocators.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use mem::ptr::NonNull;
+    /// #
+    /// let val = "string";
+    /// let nonnull = std::ptr::NonNull::from(val);
+    ///
+    /// assert_eq!(NonNull::from_inner(nonnull), NonNull::from(val));
+    /// ```
+    #[allow(clippy::inline_always)]
+    #[must_use]
+    #[inline(always)] // transparent transmormation
+    pub const fn from_inner(ptr: std::ptr::NonNull<T>) -> Self {
+        Self(ptr)
+    }
+
     /// This is true when `NonNull<T>` is a fat pointer.
     ///
     /// # Example
@@ -0,0 +1,95 @@
+//! Compares the [`PriorityQueue`] implementations running Dijkstra's
+//! algorithm over random graphs.
+//!
+//! Usage: `dijkstra [vertices] [edges per vertex] [seed]`
+use dsrs::heap::{BinaryHeap, DaryHeap, Min, PairingHeap, PriorityQueue};
+use dsrs::random::XorShift;
+
+use std::env;
+use std::time::{Duration, Instant};
+
+/// Adjacency lists, with the target and the weight of each edge.
+type Graph = Vec<Vec<(usize, u64)>>;
+
+const MAX_WEIGHT: usize = 1000;
+const RUNS: u32 = 5;
+
+fn random_graph(vertices: usize, degree: usize, rng: &mut XorShift) -> Graph {
+    let mut edge = || (rng.below(vertices), 1 + rng.below(MAX_WEIGHT) as u64);
+    (0..vertices).map(|_| (0..degree).map(|_| edge()).collect()).collect()
+}
+
+/// Distances from `source` to every vertex, all `None` when `source` is
+/// not in the graph.
+fn dijkstra<Q>(graph: &Graph, source: usize) -> Vec<Option<u64>>
+where Q: PriorityQueue<(u64, usize), Min> + Default {
+    let mut distance = vec![None; graph.len()];
+    if source >= graph.len() {
+        return distance
+    }
+    // handle and tentative distance of the queued vertices
+    let mut queued = vec![None; graph.len()];
+    let mut queue = Q::default();
+    queued[source] = Some((queue.push((0, source)), 0));
+
+    while let Some((dist, vertex)) = queue.pop() {
+        distance[vertex] = Some(dist);
+        queued[vertex] = None;
+
+        for &(next, weight) in &graph[vertex] {
+            if distance[next].is_some() {
+                continue
+            }
+            let candidate = dist + weight;
+            match queued[next] {
+                Some((_, old)) if old <= candidate => (),
+                // SAFETY: only vertices still in the queue keep a handle
+                Some((handle, _)) => unsafe {
+                    queue.decrease_key(handle, (candidate, next));
+                    queued[next] = Some((handle, candidate))
+                },
+                None => queued[next] = Some((queue.push((candidate, next)), candidate))
+            }
+        }
+    }
+    distance
+}
+
+/// Best time over a few runs, with the distances of the last one.
+fn measure<Q>(graph: &Graph) -> (Duration, Vec<Option<u64>>)
+where Q: PriorityQueue<(u64, usize), Min> + Default {
+    let mut best = Duration::from_secs(u64::MAX);
+    let mut distance = Vec::new();
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        distance = dijkstra::<Q>(graph, 0);
+        best = best.min(start.elapsed())
+    }
+    (best, distance)
+}
+
+fn argument(position: usize, default: usize) -> usize {
+    env::args().nth(position).map_or(default, |arg| arg.parse().expect("arguments must be numbers"))
+}
+
+fn main() {
+    let vertices = argument(1, 100_000);
+    let degree = argument(2, 8);
+    let seed = argument(3, 0) as u64;
+
+    let graph = random_graph(vertices, degree, &mut XorShift::new(seed));
+    println!("{} vertices, {} edges each, best of {} runs", vertices, degree, RUNS);
+
+    let (time, expected) = measure::<BinaryHeap<_, Min>>(&graph);
+    println!("{:>12}: {:?}", "binary", time);
+
+    let results = vec![
+        ("4-ary", measure::<DaryHeap<_, Min, 4>>(&graph)),
+        ("8-ary", measure::<DaryHeap<_, Min, 8>>(&graph)),
+        ("pairing", measure::<PairingHeap<_>>(&graph)),
+    ];
+    for (name, (time, distance)) in results {
+        assert_eq!(distance, expected, "{} found different distances", name);
+        println!("{:>12}: {:?}", name, time)
+    }
+}
@@ -0,0 +1,767 @@
+//! Double-ended queue over a ring buffer.
+use mem::alloc::{grow, Layout};
+use mem::ptr::NonNull;
+
+use std::alloc::{handle_alloc_error, AllocRef, Global};
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::{FromIterator, FusedIterator};
+use std::marker::PhantomData;
+use std::mem::{size_of, MaybeUninit};
+use std::ops::{Index, IndexMut, Range};
+use std::{ptr, slice};
+
+/// Capacity of the first allocation.
+const MIN_CAPACITY: usize = 4;
+/// Largest power of two, used as the capacity for zero-sized values.
+const MAX_CAPACITY: usize = !(usize::MAX >> 1);
+
+/// Double-ended queue with *O*(1) pushes and pops at both ends.
+///
+/// Values live in a single buffer, starting from `head` and wrapping around
+/// its end. The capacity is always a power of two, so that positions are
+/// wrapped with a mask.
+pub struct RingDeque<T> {
+    ptr: NonNull<T>,
+    // zero or a power of two
+    capacity: usize,
+    head: usize,
+    length: usize,
+    marker: PhantomData<T>
+}
+
+// SAFETY: the deque owns its values, just like a 'Vec'
+unsafe impl<T: Send> Send for RingDeque<T> {}
+// SAFETY: shared references only give shared access to the values
+unsafe impl<T: Sync> Sync for RingDeque<T> {}
+
+impl<T> RingDeque<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        let capacity = if size_of::<T>() == 0 { MAX_CAPACITY } else { 0 };
+        Self { ptr: NonNull::dangling(), capacity, head: 0, length: 0, marker: PhantomData }
+    }
+
+    /// Creates an empty deque with room for at least `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// If the capacity overflows.
+    #[must_use]
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut deque = Self::new();
+        deque.reserve(capacity);
+        deque
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Position in the buffer of the value at `index`.
+    #[inline]
+    const fn slot(&self, index: usize) -> usize {
+        (self.head + index) & self.capacity.wrapping_sub(1)
+    }
+
+    /// # Safety
+    ///
+    /// The buffer must be allocated.
+    #[inline]
+    unsafe fn slot_ptr(&self, index: usize) -> *mut T {
+        // SAFETY: every slot is inside the buffer
+        unsafe { self.ptr.as_ptr().add(self.slot(index)) }
+    }
+
+    /// Ranges of the buffer with values, from the front to the back.
+    #[inline]
+    const fn ranges(&self) -> (Range<usize>, Range<usize>) {
+        let end = self.head + self.length;
+        if end <= self.capacity {
+            (self.head..end, 0..0)
+        } else {
+            (self.head..self.capacity, 0..end - self.capacity)
+        }
+    }
+
+    /// Moves the values to a larger buffer of `capacity` slots, keeping
+    /// them in order.
+    fn grow_to(&mut self, capacity: usize) {
+        debug_assert!(capacity.is_power_of_two() && capacity > self.capacity);
+        debug_assert!(size_of::<T>() > 0);
+        let new_layout =
+            Layout::array::<T>(capacity).unwrap_or_else(|_| panic!("capacity overflow"));
+
+        let block = if self.capacity == 0 {
+            Global.alloc(new_layout.inner()).map(|block| NonNull::from_inner(block.cast()))
+        } else {
+            let old_size = self.capacity * size_of::<T>();
+            // SAFETY: the current capacity was already allocated
+            let old_layout =
+                unsafe { Layout::from_size_align_unchecked(old_size, new_layout.align()) };
+            // SAFETY: the block was allocated by 'Global' with 'old_layout'
+            unsafe { grow(self.ptr, old_layout, new_layout) }
+        };
+        self.ptr = block.unwrap_or_else(|_| handle_alloc_error(new_layout.inner()));
+        let old_capacity = std::mem::replace(&mut self.capacity, capacity);
+
+        if self.head + self.length > old_capacity {
+            // the values wrapped around the old end, so either the back
+            // goes after the front or the front goes to the new end
+            let front = old_capacity - self.head;
+            let back = self.length - front;
+            // SAFETY: at least the old capacity was added, so both moves
+            // go to free slots
+            unsafe {
+                let buffer = self.ptr.as_ptr();
+                if back <= front {
+                    ptr::copy_nonoverlapping(buffer, buffer.add(old_capacity), back)
+                } else {
+                    let head = capacity - front;
+                    ptr::copy_nonoverlapping(buffer.add(self.head), buffer.add(head), front);
+                    self.head = head
+                }
+            }
+        }
+    }
+
+    /// Makes room for at least `additional` more values.
+    ///
+    /// # Panics
+    ///
+    /// If the capacity overflows.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.length.checked_add(additional).expect("capacity overflow");
+        if required > self.capacity {
+            let capacity = required.checked_next_power_of_two().expect("capacity overflow");
+            self.grow_to(capacity.max(MIN_CAPACITY))
+        }
+    }
+
+    #[inline]
+    pub fn push_back(&mut self, value: T) {
+        self.reserve(1);
+        // SAFETY: there is a free slot after the back
+        unsafe { self.slot_ptr(self.length).write(value) };
+        self.length += 1
+    }
+
+    #[inline]
+    pub fn push_front(&mut self, value: T) {
+        self.reserve(1);
+        self.head = self.slot(self.capacity - 1);
+        // SAFETY: there was a free slot before the front
+        unsafe { self.slot_ptr(0).write(value) };
+        self.length += 1
+    }
+
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None
+        }
+        // SAFETY: the front is initialized, and its slot is freed below
+        let value = unsafe { self.slot_ptr(0).read() };
+        self.head = self.slot(1);
+        self.length -= 1;
+        Some(value)
+    }
+
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None
+        }
+        self.length -= 1;
+        // SAFETY: the back was initialized, and its slot is now free
+        Some(unsafe { self.slot_ptr(self.length).read() })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.length {
+            // SAFETY: the value at 'index' is initialized
+            Some(unsafe { &*self.slot_ptr(index) })
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.length {
+            // SAFETY: the value at 'index' is initialized and the deque is
+            // mutably borrowed
+            Some(unsafe { &mut *self.slot_ptr(index) })
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.length.wrapping_sub(1))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.length.wrapping_sub(1))
+    }
+
+    /// Values in order, split where they wrap around the buffer. The
+    /// second slice is empty when the values are contiguous.
+    #[must_use]
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.ranges();
+        // SAFETY: both ranges are initialized and inside the buffer
+        unsafe {
+            let front = slice::from_raw_parts(self.ptr.as_ptr().add(front.start), front.len());
+            let back = slice::from_raw_parts(self.ptr.as_ptr().add(back.start), back.len());
+            (front, back)
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.ranges();
+        // SAFETY: both ranges are initialized, inside the buffer and
+        // disjoint
+        unsafe {
+            let front = slice::from_raw_parts_mut(self.ptr.as_ptr().add(front.start), front.len());
+            let back = slice::from_raw_parts_mut(self.ptr.as_ptr().add(back.start), back.len());
+            (front, back)
+        }
+    }
+
+    /// Moves the values so that they don't wrap around the buffer, which
+    /// takes *O*(capacity) when they do.
+    #[inline]
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if size_of::<T>() == 0 {
+            // any position holds the same value
+            self.head = 0
+        } else if self.head + self.length > self.capacity {
+            let slots = self.ptr.as_ptr().cast::<MaybeUninit<T>>();
+            // SAFETY: the buffer is allocated, and moving its slots keeps
+            // the values in the same cyclic order
+            let buffer = unsafe { slice::from_raw_parts_mut(slots, self.capacity) };
+            buffer.rotate_left(self.head);
+            self.head = 0
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Moves the front value after the back one.
+    #[inline]
+    fn cycle_front(&mut self) {
+        debug_assert!(self.length > 0);
+        // SAFETY: the slot after the back is free, unless the buffer is
+        // full, when it is the front slot itself
+        unsafe { ptr::copy(self.slot_ptr(0), self.slot_ptr(self.length), 1) };
+        self.head = self.slot(1)
+    }
+
+    /// Moves the back value before the front one.
+    #[inline]
+    fn cycle_back(&mut self) {
+        debug_assert!(self.length > 0);
+        self.head = self.slot(self.capacity - 1);
+        // SAFETY: same as above, with the back now after the last index
+        unsafe { ptr::copy(self.slot_ptr(self.length), self.slot_ptr(0), 1) }
+    }
+
+    /// Rotates the values `n` places to the front, so the value at `n`
+    /// becomes the first.
+    ///
+    /// Takes *O*(min(`n`, `len - n`)) moves.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is greater than the length.
+    #[inline]
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.length, "cannot rotate by more than the length");
+        let rest = self.length - n;
+        if n <= rest {
+            (0..n).for_each(|_| self.cycle_front())
+        } else {
+            (0..rest).for_each(|_| self.cycle_back())
+        }
+    }
+
+    /// Rotates the values `n` places to the back, so the last `n` values
+    /// become the first.
+    ///
+    /// Takes *O*(min(`n`, `len - n`)) moves.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is greater than the length.
+    #[inline]
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.length, "cannot rotate by more than the length");
+        self.rotate_left(self.length - n)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back): (*mut [T], *mut [T]) = (front, back);
+        self.head = 0;
+        self.length = 0;
+        // SAFETY: the values are no longer reachable, and are dropped once
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back)
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+        Iter { front: front.iter(), back: back.iter() }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+        IterMut { front: front.iter_mut(), back: back.iter_mut() }
+    }
+}
+
+impl<T> Drop for RingDeque<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+        if size_of::<T>() > 0 && self.capacity > 0 {
+            // SAFETY: the block was allocated by 'Global' with this layout
+            unsafe {
+                let size = self.capacity * size_of::<T>();
+                let layout = Layout::from_size_align_unchecked(size, Layout::new::<T>().align());
+                Global.dealloc(self.ptr.cast().inner(), layout.inner())
+            }
+        }
+    }
+}
+
+impl<T> Default for RingDeque<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for RingDeque<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut deque = Self::with_capacity(self.length);
+        deque.extend(self.iter().cloned());
+        deque
+    }
+}
+
+impl<T: Debug> Debug for RingDeque<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for RingDeque<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for RingDeque<T> {}
+
+impl<T> Index<usize> for RingDeque<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for RingDeque<T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T> FromIterator<T> for RingDeque<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T> Extend<T> for RingDeque<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push_back(value)
+        }
+    }
+}
+
+/// Iterator over references to the values of a [`RingDeque`].
+pub struct Iter<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>
+}
+
+impl<T> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { front: self.front.clone(), back: self.back.clone() }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// Iterator over mutable references to the values of a [`RingDeque`].
+pub struct IterMut<'a, T> {
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+impl<T: Debug> Debug for IterMut<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("IterMut").field(&self.front.as_slice()).field(&self.back.as_slice()).finish()
+    }
+}
+
+/// Owning iterator over the values of a [`RingDeque`].
+#[derive(Clone, Debug)]
+pub struct IntoIter<T> {
+    deque: RingDeque<T>
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len(), Some(self.deque.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for RingDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingDeque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut RingDeque<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    /// Deque with its values wrapped around the buffer end.
+    fn wrapped() -> RingDeque<i32> {
+        let mut deque = RingDeque::with_capacity(8);
+        deque.extend(3..8);
+        for value in (0..3).rev() {
+            deque.push_front(value)
+        }
+        deque
+    }
+
+    #[test]
+    fn both_ends() {
+        let mut deque = RingDeque::new();
+        assert_eq!((deque.pop_front(), deque.pop_back()), (None::<i32>, None));
+        assert_eq!(deque.capacity(), 0);
+
+        deque.push_back(1);
+        deque.push_front(0);
+        deque.push_back(2);
+        assert_eq!((deque.front(), deque.back(), deque.len()), (Some(&0), Some(&2), 3));
+        assert_eq!(deque.capacity(), MIN_CAPACITY);
+        assert_eq!(deque.pop_back(), Some(2));
+        assert_eq!(deque.pop_front(), Some(0));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert!(deque.is_empty())
+    }
+
+    #[test]
+    fn grows_wrapped() {
+        let mut deque = wrapped();
+        assert_eq!(deque.capacity(), 8);
+        let (front, back) = deque.as_slices();
+        assert_eq!((front, back), (&[0, 1, 2][..], &[3, 4, 5, 6, 7][..]));
+
+        deque.push_front(-1);
+        deque.push_back(8);
+        assert_eq!(deque.capacity(), 16);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), (-1..=8).collect::<Vec<_>>());
+
+        let mut other = wrapped();
+        other.extend(8..10);
+        assert!(other.iter().rev().copied().eq((0..10).rev()));
+
+        // shorter part wrapped, so it moves after the front
+        let mut deque: RingDeque<_> = (0..8).collect();
+        deque.pop_front();
+        deque.pop_front();
+        for value in 8..11 {
+            deque.push_back(value)
+        }
+        assert_eq!(deque.as_slices(), (&(2..11).collect::<Vec<_>>()[..], &[][..]))
+    }
+
+    #[test]
+    fn contiguous() {
+        let mut deque = wrapped();
+        assert_eq!(deque.make_contiguous(), &[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(deque.as_slices().1, &[]);
+
+        let mut deque = wrapped();
+        deque.pop_back();
+        deque.pop_back();
+        assert_eq!(deque.make_contiguous(), &[0, 1, 2, 3, 4, 5]);
+        deque.make_contiguous().reverse();
+        assert_eq!(deque.pop_front(), Some(5))
+    }
+
+    #[test]
+    fn rotate_like_vec_deque() {
+        for len in 0..10 {
+            for n in 0..=len {
+                let mut deque: RingDeque<_> = (0..len).collect();
+                let mut model: VecDeque<_> = (0..len).collect();
+                deque.push_front(len);
+                model.push_front(len);
+                deque.rotate_left(n);
+                model.rotate_left(n);
+                assert!(deque.iter().eq(model.iter()));
+                deque.rotate_right(n);
+                model.rotate_right(n);
+                assert!(deque.iter().eq(model.iter()))
+            }
+        }
+    }
+
+    #[test]
+    fn indexing() {
+        let mut deque = wrapped();
+        assert_eq!((deque[0], deque[4]), (0, 4));
+        deque[7] *= 10;
+        assert_eq!(deque.get(7), Some(&70));
+        assert_eq!(deque.get(8), None);
+
+        for value in &mut deque {
+            *value += 1
+        }
+        assert_eq!(format!("{:?}", deque), "[1, 2, 3, 4, 5, 6, 7, 71]")
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn index_past_the_end() {
+        let deque = wrapped();
+        let _ = deque[8];
+    }
+
+    #[test]
+    fn drops_every_value() {
+        let counter = Rc::new(());
+        let mut deque = RingDeque::new();
+        for _ in 0..5 {
+            deque.push_front(Rc::clone(&counter));
+            deque.push_back(Rc::clone(&counter))
+        }
+        drop(deque.pop_front());
+        let clone = deque.clone();
+        assert_eq!(Rc::strong_count(&counter), 19);
+
+        drop(deque);
+        let mut iter = clone.into_iter();
+        iter.next_back();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&counter), 1)
+    }
+
+    #[test]
+    fn zero_sized_values() {
+        let mut deque = RingDeque::new();
+        for _ in 0..10 {
+            deque.push_front(())
+        }
+        deque.rotate_left(3);
+        assert_eq!((deque.len(), deque.make_contiguous().len()), (10, 10));
+        assert_eq!(deque.into_iter().count(), 10)
+    }
+
+    #[test]
+    fn breadth_first_search() {
+        let graph: &[&[usize]] = &[&[1, 2], &[3], &[3, 4], &[5], &[5], &[]];
+        let mut distance = vec![None; graph.len()];
+        let mut queue = RingDeque::new();
+
+        distance[0] = Some(0);
+        queue.push_back(0);
+        while let Some(vertex) = queue.pop_front() {
+            for &next in graph[vertex] {
+                if distance[next].is_none() {
+                    distance[next] = distance[vertex].map(|d| d + 1);
+                    queue.push_back(next)
+                }
+            }
+        }
+        assert_eq!(distance, vec![Some(0), Some(1), Some(1), Some(2), Some(2), Some(3)])
+    }
+}
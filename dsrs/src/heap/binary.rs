@@ -0,0 +1,424 @@
+use super::compare::{Compare, Max};
+use super::dary::{DaryHandle, DaryHeap};
+use super::priority_queue::PriorityQueue;
+use super::sift::{self, Ordered};
+
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+/// Sorts `data` in ascending order of `cmp`, in place and in *O*(n log n).
+#[inline]
+pub fn heapsort_by<T, C: Compare<T>>(data: &mut [T], cmp: &C) {
+    let len = data.len();
+    let mut heap = Ordered { data, cmp };
+    sift::heapify(&mut heap, len, 2);
+    sift::sort(&mut heap, len, 2)
+}
+
+/// Sorts `data` in ascending order, in place and in *O*(n log n).
+#[inline]
+pub fn heapsort<T: Ord>(data: &mut [T]) {
+    heapsort_by(data, &Max)
+}
+
+/// Priority queue over an implicit binary tree, with the greatest value
+/// according to `C` at the top.
+///
+/// This is the binary case of [`DaryHeap`], so pushes also return handles
+/// to move values up with [`decrease_key`](BinaryHeap::decrease_key).
+pub struct BinaryHeap<T, C: Compare<T> = Max> {
+    inner: DaryHeap<T, C, 2>
+}
+
+impl<T: Ord> BinaryHeap<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_comparator(Max)
+    }
+}
+
+impl<T, C: Compare<T>> BinaryHeap<T, C> {
+    #[must_use]
+    #[inline]
+    pub const fn with_comparator(cmp: C) -> Self {
+        Self { inner: DaryHeap::with_comparator(cmp) }
+    }
+
+    /// Builds a heap from the values of `data`, in *O*(n).
+    #[must_use]
+    #[inline]
+    pub fn from_vec(data: Vec<T>) -> Self where C: Default {
+        Self::from_vec_with(data, C::default())
+    }
+
+    /// Same as [`from_vec`](BinaryHeap::from_vec), with a given comparator.
+    #[must_use]
+    #[inline]
+    pub fn from_vec_with(data: Vec<T>, cmp: C) -> Self {
+        Self { inner: DaryHeap::from_vec_with(data, cmp) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn comparator(&self) -> &C {
+        self.inner.comparator()
+    }
+
+    /// Greatest value, in *O*(1).
+    #[must_use]
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.inner.peek()
+    }
+
+    /// Value of `handle`, if it was not popped yet.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, handle: DaryHandle) -> Option<&T> {
+        self.inner.get(handle)
+    }
+
+    /// Mutable access to the greatest value, which is moved to its new
+    /// position when the [`PeekMut`] is dropped.
+    #[must_use]
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self, changed: false })
+        }
+    }
+
+    /// Inserts a value in *O*(log n).
+    #[inline]
+    pub fn push(&mut self, value: T) -> DaryHandle {
+        self.inner.push(value)
+    }
+
+    /// Removes the greatest value in *O*(log n).
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    /// Moves every value of `other` into the heap, leaving it empty.
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        self.inner.append(&mut other.inner)
+    }
+
+    /// Moves the value of `handle` up, in *O*(log n).
+    ///
+    /// # Panics
+    ///
+    /// If the value of `handle` was popped or if `value` is less than it
+    /// according to the comparator.
+    #[inline]
+    pub fn decrease_key(&mut self, handle: DaryHandle, value: T) {
+        self.inner.decrease_key(handle, value)
+    }
+
+    /// Removes every value, invalidating all handles.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.clear()
+    }
+
+    /// Iterates over the values in no particular order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.inner.iter()
+    }
+
+    /// Values in heap order.
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+
+    /// Values in heap order.
+    #[must_use]
+    #[inline]
+    pub fn into_vec(self) -> Vec<T> {
+        self.inner.into_vec()
+    }
+
+    /// Values in ascending order, in *O*(n log n).
+    #[must_use]
+    #[inline]
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.inner.into_sorted_vec()
+    }
+}
+
+/// Mutable reference to the top of a [`BinaryHeap`], created by
+/// [`BinaryHeap::peek_mut`].
+///
+/// The value is sifted down on drop, if it was changed.
+pub struct PeekMut<'a, T, C: Compare<T>> {
+    heap: &'a mut BinaryHeap<T, C>,
+    changed: bool
+}
+
+impl<T, C: Compare<T>> PeekMut<'_, T, C> {
+    /// Removes the peeked value from the heap.
+    #[must_use]
+    #[inline]
+    pub fn pop(mut this: Self) -> T {
+        this.changed = false;
+        match this.heap.pop() {
+            Some(value) => value,
+            None => unreachable!("peeked heap is empty")
+        }
+    }
+}
+
+impl<T, C: Compare<T>> Deref for PeekMut<'_, T, C> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.heap.inner.data[0]
+    }
+}
+
+impl<T, C: Compare<T>> DerefMut for PeekMut<'_, T, C> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.changed = true;
+        &mut self.heap.inner.data[0]
+    }
+}
+
+impl<T, C: Compare<T>> Drop for PeekMut<'_, T, C> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.changed {
+            self.heap.inner.sift_down(0)
+        }
+    }
+}
+
+impl<T: Debug, C: Compare<T>> Debug for PeekMut<'_, T, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("PeekMut").field(&**self).finish()
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T, C> for BinaryHeap<T, C> {
+    type Handle = DaryHandle;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn comparator(&self) -> &C {
+        self.comparator()
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    #[inline]
+    fn push(&mut self, value: T) -> DaryHandle {
+        self.push(value)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    #[inline]
+    fn meld(&mut self, mut other: Self) {
+        self.append(&mut other)
+    }
+
+    #[inline]
+    unsafe fn decrease_key(&mut self, handle: DaryHandle, value: T) {
+        self.decrease_key(handle, value)
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for BinaryHeap<T, C> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T: Clone, C: Compare<T> + Clone> Clone for BinaryHeap<T, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<T: Debug, C: Compare<T>> Debug for BinaryHeap<T, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T, C: Compare<T> + Default> From<Vec<T>> for BinaryHeap<T, C> {
+    #[inline]
+    fn from(data: Vec<T>) -> Self {
+        Self::from_vec(data)
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for BinaryHeap<T, C> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for BinaryHeap<T, C> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.inner.extend(iter)
+    }
+}
+
+impl<T, C: Compare<T>> IntoIterator for BinaryHeap<T, C> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Iterates over the values in no particular order.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'a, T, C: Compare<T>> IntoIterator for &'a BinaryHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::compare::{by, Min};
+    use super::*;
+    use std::cmp::Ordering;
+    use std::mem::size_of_val;
+
+    fn is_heap<T, C: Compare<T>>(heap: &BinaryHeap<T, C>) -> bool {
+        let (data, cmp) = (heap.as_slice(), heap.comparator());
+        let ordered = |index: usize| cmp.compare(&data[(index - 1) / 2], &data[index]);
+        (1..data.len()).all(|index| ordered(index) != Ordering::Less)
+    }
+
+    fn shuffled(len: u64) -> Vec<u64> {
+        (0..len).map(|value| value * 7919 % len).collect()
+    }
+
+    #[test]
+    fn max_heap() {
+        let mut heap = BinaryHeap::new();
+        assert_eq!((heap.pop(), heap.peek()), (None::<u64>, None));
+
+        for value in shuffled(101) {
+            heap.push(value);
+            assert!(is_heap(&heap))
+        }
+        assert_eq!((heap.len(), heap.peek()), (101, Some(&100)));
+        for expected in (0..101).rev() {
+            assert_eq!(heap.pop(), Some(expected));
+            assert!(is_heap(&heap))
+        }
+        assert!(heap.is_empty())
+    }
+
+    #[test]
+    fn comparators() {
+        let mut min = BinaryHeap::<_, Min>::from_vec(shuffled(20));
+        assert!(is_heap(&min));
+        assert_eq!(min.pop(), Some(0));
+
+        let by_last_digit = by(|a: &u64, b: &u64| (a % 10).cmp(&(b % 10)).then(b.cmp(a)));
+        let mut heap = BinaryHeap::from_vec_with(shuffled(30), by_last_digit);
+        assert_eq!((heap.pop(), heap.pop(), heap.pop()), (Some(9), Some(19), Some(29)));
+
+        assert_eq!(size_of_val(&Max) + size_of_val(&Min) + size_of_val(&by_last_digit), 0);
+        assert_eq!(size_of_val(&heap), size_of_val(&BinaryHeap::<u64>::new()));
+        min.clear()
+    }
+
+    #[test]
+    fn peek_mut_sifts_on_drop() {
+        let mut heap: BinaryHeap<_> = shuffled(10).into_iter().collect();
+        *heap.peek_mut().unwrap() = 3;
+        assert!(is_heap(&heap));
+        assert_eq!(heap.peek(), Some(&8));
+
+        {
+            let top = heap.peek_mut().unwrap();
+            assert_eq!(*top, 8);
+        }
+        assert_eq!(PeekMut::pop(heap.peek_mut().unwrap()), 8);
+        assert_eq!(heap.into_sorted_vec(), vec![0, 1, 2, 3, 3, 4, 5, 6, 7])
+    }
+
+    #[test]
+    fn append_both_ways() {
+        for (left, right) in [(0, 0), (1, 40), (40, 1), (30, 30), (200, 5)].iter().copied() {
+            let mut heap = BinaryHeap::<_, Min>::from_vec(shuffled(left));
+            let mut other = BinaryHeap::from_vec(shuffled(right));
+            heap.append(&mut other);
+            assert!(other.is_empty() && is_heap(&heap));
+
+            let mut expected: Vec<_> = shuffled(left).into_iter().chain(shuffled(right)).collect();
+            expected.sort_unstable_by(|a, b| b.cmp(a));
+            assert_eq!(heap.into_sorted_vec(), expected)
+        }
+    }
+
+    #[test]
+    fn extend_keeps_heap() {
+        let mut heap: BinaryHeap<_> = BinaryHeap::from_vec(vec![5, 1]);
+        heap.extend(vec![7, 3, 9]);
+        assert!(is_heap(&heap));
+        assert_eq!(heap.into_sorted_vec(), vec![1, 3, 5, 7, 9])
+    }
+
+    #[test]
+    fn sorts() {
+        for len in 0..50 {
+            let mut data = shuffled(len);
+            heapsort(&mut data);
+            assert_eq!(data, (0..len).collect::<Vec<_>>());
+            heapsort_by(&mut data, &Min);
+            assert_eq!(data, (0..len).rev().collect::<Vec<_>>())
+        }
+    }
+}
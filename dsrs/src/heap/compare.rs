@@ -0,0 +1,48 @@
+use std::cmp::Ordering;
+
+/// Order used by a heap, which keeps its greatest value at the top.
+pub trait Compare<T: ?Sized> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Natural order, for max-heaps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Max;
+
+impl<T: ?Sized + Ord> Compare<T> for Max {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Reversed order, for min-heaps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Min;
+
+impl<T: ?Sized + Ord> Compare<T> for Min {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/// Order given by a closure, zero-sized when it captures nothing.
+///
+/// Usually built with [`by`], which helps inferring the closure signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FnCompare<F>(pub F);
+
+impl<T: ?Sized, F: Fn(&T, &T) -> Ordering> Compare<T> for FnCompare<F> {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+/// Comparator from a closure.
+#[must_use]
+#[inline]
+pub fn by<T: ?Sized, F: Fn(&T, &T) -> Ordering>(compare: F) -> FnCompare<F> {
+    FnCompare(compare)
+}
@@ -0,0 +1,412 @@
+use super::compare::{Compare, Min};
+use super::priority_queue::PriorityQueue;
+use super::sift::{self, Ordered, Sift};
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FromIterator;
+use std::slice;
+
+/// Position of the handles whose values were popped.
+const POPPED: usize = usize::MAX;
+
+/// Handle to a value in a [`DaryHeap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DaryHandle(usize);
+
+/// Priority queue over an implicit tree where each node has `D` children,
+/// with the greatest value according to `C` at the top.
+///
+/// Larger arities make pushes and [`decrease_key`](DaryHeap::decrease_key)
+/// cheaper, as the tree is shallower, while pops compare more children
+/// per level.
+///
+/// Each push returns a handle to its value. Once that value is popped, the
+/// handle may be given to a later push, so it must not be used anymore.
+pub struct DaryHeap<T, C: Compare<T>, const D: usize> {
+    // values in heap order
+    pub(super) data: Vec<T>,
+    // handle of each value in 'data'
+    handles: Vec<usize>,
+    // position in 'data' of each handle
+    positions: Vec<usize>,
+    // handles whose values were popped
+    free: Vec<usize>,
+    cmp: C
+}
+
+impl<T: Ord, const D: usize> DaryHeap<T, Min, D> {
+    /// Creates an empty min-heap.
+    ///
+    /// # Panics
+    ///
+    /// If `D` is less than 2.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_comparator(Min)
+    }
+}
+
+impl<T, C: Compare<T>, const D: usize> DaryHeap<T, C, D> {
+    /// # Panics
+    ///
+    /// If `D` is less than 2.
+    #[must_use]
+    #[inline]
+    pub const fn with_comparator(cmp: C) -> Self {
+        assert!(D >= 2, "the arity must be at least 2");
+        Self { data: Vec::new(), handles: Vec::new(), positions: Vec::new(), free: Vec::new(), cmp }
+    }
+
+    /// Builds a heap from the values of `data`, in *O*(n).
+    #[must_use]
+    #[inline]
+    pub fn from_vec_with(data: Vec<T>, cmp: C) -> Self {
+        let mut heap = Self::with_comparator(cmp);
+        heap.append_vec(data);
+        heap
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Greatest value, in *O*(1).
+    #[must_use]
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    #[inline]
+    fn position(&self, handle: DaryHandle) -> Option<usize> {
+        match self.positions.get(handle.0) {
+            Some(&pos) if pos != POPPED => Some(pos),
+            _ => None
+        }
+    }
+
+    /// Value of `handle`, if it was not popped yet.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, handle: DaryHandle) -> Option<&T> {
+        self.position(handle).map(|pos| &self.data[pos])
+    }
+
+    /// Adds a value at the end, without moving it to its place.
+    #[inline]
+    fn push_last(&mut self, value: T) -> DaryHandle {
+        let pos = self.data.len();
+        let handle = if let Some(handle) = self.free.pop() {
+            self.positions[handle] = pos;
+            handle
+        } else {
+            self.positions.push(pos);
+            self.positions.len() - 1
+        };
+        self.data.push(value);
+        self.handles.push(handle);
+        DaryHandle(handle)
+    }
+
+    /// Adds every value of `values` and restores the heap order.
+    #[inline]
+    fn append_vec(&mut self, values: Vec<T>) {
+        let start = self.len();
+        self.data.reserve(values.len());
+        for value in values {
+            self.push_last(value);
+        }
+        let len = self.len();
+        sift::sift_appended(self, start, len, D)
+    }
+
+    #[inline]
+    pub(super) fn sift_down(&mut self, pos: usize) {
+        let len = self.len();
+        sift::sift_down(self, pos, len, D);
+    }
+
+    /// Inserts a value in *O*(log n / log D).
+    #[inline]
+    pub fn push(&mut self, value: T) -> DaryHandle {
+        let handle = self.push_last(value);
+        let last = self.len() - 1;
+        sift::sift_up(self, last, D);
+        handle
+    }
+
+    /// Removes the greatest value in *O*(D log n / log D).
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None
+        }
+        let value = self.data.swap_remove(0);
+        let handle = self.handles.swap_remove(0);
+        self.positions[handle] = POPPED;
+        self.free.push(handle);
+        if let Some(&last) = self.handles.first() {
+            self.positions[last] = 0;
+            self.sift_down(0)
+        }
+        Some(value)
+    }
+
+    /// Moves every value of `other` into the heap, with new handles,
+    /// leaving it empty.
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        let values = std::mem::take(&mut other.data);
+        other.clear();
+        self.append_vec(values)
+    }
+
+    /// Moves every value of `other` into the heap, with new handles.
+    #[inline]
+    pub fn meld(&mut self, mut other: Self) {
+        self.append(&mut other)
+    }
+
+    /// Moves the value of `handle` up, in *O*(log n / log D).
+    ///
+    /// # Panics
+    ///
+    /// If the value of `handle` was popped or if `value` is less than it
+    /// according to the comparator.
+    #[inline]
+    pub fn decrease_key(&mut self, handle: DaryHandle, value: T) {
+        let pos = self.position(handle).expect("the value of this handle was popped");
+        let current = &mut self.data[pos];
+        assert_ne!(
+            self.cmp.compare(&value, current),
+            Ordering::Less,
+            "the new value is lower in the heap than the current one"
+        );
+        *current = value;
+        sift::sift_up(self, pos, D);
+    }
+
+    /// Removes every value, invalidating all handles.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.handles.clear();
+        self.positions.clear();
+        self.free.clear()
+    }
+
+    /// Iterates over the values in no particular order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Values in heap order.
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Values in heap order.
+    #[must_use]
+    #[inline]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Values in ascending order, in *O*(D n log n / log D).
+    #[must_use]
+    #[inline]
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let len = self.len();
+        sift::sort(&mut Ordered { data: &mut self.data, cmp: &self.cmp }, len, D);
+        self.data
+    }
+}
+
+impl<T, C: Compare<T>, const D: usize> Sift for DaryHeap<T, C, D> {
+    #[inline]
+    fn higher(&self, a: usize, b: usize) -> bool {
+        self.cmp.compare(&self.data[a], &self.data[b]) == Ordering::Greater
+    }
+
+    #[inline]
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+        self.handles.swap(a, b);
+        self.positions[self.handles[a]] = a;
+        self.positions[self.handles[b]] = b
+    }
+}
+
+impl<T, C: Compare<T>, const D: usize> PriorityQueue<T, C> for DaryHeap<T, C, D> {
+    type Handle = DaryHandle;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn comparator(&self) -> &C {
+        self.comparator()
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    #[inline]
+    fn push(&mut self, value: T) -> DaryHandle {
+        self.push(value)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    #[inline]
+    fn meld(&mut self, other: Self) {
+        self.meld(other)
+    }
+
+    #[inline]
+    unsafe fn decrease_key(&mut self, handle: DaryHandle, value: T) {
+        self.decrease_key(handle, value)
+    }
+}
+
+impl<T, C: Compare<T> + Default, const D: usize> Default for DaryHeap<T, C, D> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T: Clone, C: Compare<T> + Clone, const D: usize> Clone for DaryHeap<T, C, D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            handles: self.handles.clone(),
+            positions: self.positions.clone(),
+            free: self.free.clone(),
+            cmp: self.cmp.clone()
+        }
+    }
+}
+
+impl<T: Debug, C: Compare<T>, const D: usize> Debug for DaryHeap<T, C, D> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, C: Compare<T> + Default, const D: usize> From<Vec<T>> for DaryHeap<T, C, D> {
+    #[inline]
+    fn from(data: Vec<T>) -> Self {
+        Self::from_vec_with(data, C::default())
+    }
+}
+
+impl<T, C: Compare<T> + Default, const D: usize> FromIterator<T> for DaryHeap<T, C, D> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T, C: Compare<T>, const D: usize> Extend<T> for DaryHeap<T, C, D> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.append_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T, C: Compare<T>, const D: usize> IntoIterator for DaryHeap<T, C, D> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Iterates over the values in no particular order.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T, C: Compare<T>, const D: usize> IntoIterator for &'a DaryHeap<T, C, D> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::compare::Max;
+    use super::*;
+
+    #[test]
+    fn handles_follow_values() {
+        let mut heap = DaryHeap::<_, Min, 4>::new();
+        let handles: Vec<_> = (0..20).rev().map(|value| heap.push(value)).collect();
+        assert_eq!(heap.get(handles[0]), Some(&19));
+
+        heap.decrease_key(handles[0], 0);
+        assert_eq!(heap.pop(), Some(0));
+        assert_eq!(heap.pop(), Some(0));
+        assert_eq!((heap.get(handles[0]), heap.get(handles[19])), (None, None));
+        assert_eq!(heap.get(handles[10]), Some(&9));
+
+        let mut other = DaryHeap::new();
+        other.push(-1);
+        heap.meld(other);
+        assert_eq!((heap.len(), heap.peek()), (19, Some(&-1)));
+        heap.clear();
+        assert_eq!(heap.get(handles[10]), None)
+    }
+
+    #[test]
+    fn handles_are_reused() {
+        let mut heap: DaryHeap<_, Max, 3> = (0..10).collect();
+        for round in 0..100 {
+            let handle = heap.push(round);
+            heap.decrease_key(handle, 10 + round);
+            assert_eq!(heap.pop(), Some(10 + round))
+        }
+        assert_eq!((heap.positions.len(), heap.free.len()), (11, 1));
+        assert_eq!(heap.into_sorted_vec(), (0..10).collect::<Vec<_>>())
+    }
+
+    #[test]
+    #[should_panic(expected = "was popped")]
+    fn decrease_popped() {
+        let mut heap = DaryHeap::<_, Min, 2>::new();
+        let handle = heap.push('a');
+        heap.pop();
+        heap.decrease_key(handle, 'a')
+    }
+}
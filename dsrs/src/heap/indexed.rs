@@ -0,0 +1,365 @@
+use super::sift::{self, Sift};
+
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+
+#[derive(Clone)]
+struct Entry<P> {
+    handle: usize,
+    priority: P
+}
+
+/// Min-heap of dense `usize` handles, each with a priority that can be
+/// changed in *O*(log n), like the frontier of Dijkstra's algorithm.
+///
+/// The memory used grows with the largest handle inserted.
+#[derive(Clone)]
+pub struct IndexedHeap<P: Ord> {
+    heap: Vec<Entry<P>>,
+    // position of each handle in 'heap'
+    index: Vec<Option<usize>>
+}
+
+impl<P: Ord> IndexedHeap<P> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { heap: Vec::new(), index: Vec::new() }
+    }
+
+    /// Creates an empty heap with room for the handles up to `handles`.
+    #[must_use]
+    #[inline]
+    pub fn with_handles(handles: usize) -> Self {
+        Self { heap: Vec::with_capacity(handles), index: vec![None; handles] }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    #[inline]
+    fn position(&self, handle: usize) -> Option<usize> {
+        self.index.get(handle).copied().flatten()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, handle: usize) -> bool {
+        self.position(handle).is_some()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn priority(&self, handle: usize) -> Option<&P> {
+        self.position(handle).map(|pos| &self.heap[pos].priority)
+    }
+
+    /// Handle with the least priority.
+    #[must_use]
+    #[inline]
+    pub fn peek_min(&self) -> Option<(usize, &P)> {
+        self.heap.first().map(|entry| (entry.handle, &entry.priority))
+    }
+
+    /// Puts the entry at `pos` in the index.
+    #[inline]
+    fn update_index(&mut self, pos: usize) {
+        let handle = self.heap[pos].handle;
+        debug_assert!(
+            self.index[handle].is_some(),
+            "handle {} moved without being in the heap",
+            handle
+        );
+        self.index[handle] = Some(pos)
+    }
+
+    #[inline]
+    fn sift_up(&mut self, pos: usize) -> usize {
+        sift::sift_up(self, pos, 2)
+    }
+
+    #[inline]
+    fn sift_down(&mut self, pos: usize) -> usize {
+        let len = self.len();
+        sift::sift_down(self, pos, len, 2)
+    }
+
+    /// Checks that every handle is indexed at the position of its entry and
+    /// that the entries are in heap order, in *O*(n). Only in debug builds.
+    #[inline]
+    fn debug_check(&self) {
+        #[cfg(debug_assertions)]
+        {
+            for (pos, entry) in self.heap.iter().enumerate() {
+                let handle = entry.handle;
+                let indexed = self.index[handle];
+                assert_eq!(indexed, Some(pos), "handle {} is not indexed at its entry", handle);
+                if pos > 0 {
+                    let parent = &self.heap[(pos - 1) / 2];
+                    assert!(parent.priority <= entry.priority, "entry {} is above its parent", pos)
+                }
+            }
+            let indexed = self.index.iter().filter(|pos| pos.is_some()).count();
+            assert_eq!(indexed, self.heap.len(), "the index has handles that are not in the heap")
+        }
+    }
+
+    /// Moves the entry at `pos` to where its priority belongs.
+    #[inline]
+    fn restore(&mut self, pos: usize) {
+        if self.sift_up(pos) == pos {
+            self.sift_down(pos);
+        }
+    }
+
+    /// Inserts `handle` with `priority` in *O*(log n). If the handle was
+    /// already in the heap, its priority is replaced and the old one is
+    /// returned.
+    #[inline]
+    pub fn insert(&mut self, handle: usize, priority: P) -> Option<P> {
+        if let Some(pos) = self.position(handle) {
+            let old = std::mem::replace(&mut self.heap[pos].priority, priority);
+            self.restore(pos);
+            self.debug_check();
+            return Some(old)
+        }
+        if handle >= self.index.len() {
+            self.index.resize(handle + 1, None)
+        }
+        self.index[handle] = Some(self.heap.len());
+        self.heap.push(Entry { handle, priority });
+        self.sift_up(self.heap.len() - 1);
+        self.debug_check();
+        None
+    }
+
+    /// Removes `handle` in *O*(log n), returning its priority.
+    #[inline]
+    pub fn remove(&mut self, handle: usize) -> Option<P> {
+        let pos = self.index.get_mut(handle)?.take()?;
+        let entry = self.heap.swap_remove(pos);
+        debug_assert_eq!(entry.handle, handle);
+
+        if pos < self.heap.len() {
+            self.update_index(pos);
+            self.restore(pos)
+        }
+        self.debug_check();
+        Some(entry.priority)
+    }
+
+    /// Removes the handle with the least priority, in *O*(log n).
+    #[inline]
+    pub fn extract_min(&mut self) -> Option<(usize, P)> {
+        let handle = self.heap.first()?.handle;
+        self.remove(handle).map(|priority| (handle, priority))
+    }
+
+    /// Sets the priority of `handle`, checking that it moves in the
+    /// `expected` direction. Returns its position.
+    #[inline]
+    fn replace(&mut self, handle: usize, priority: P, expected: Ordering) -> usize {
+        let pos = self.position(handle).expect("handle is not in the heap");
+        let entry = &mut self.heap[pos];
+        let moved = priority.cmp(&entry.priority);
+        assert_ne!(moved, expected.reverse(), "priority changed in the wrong direction");
+        entry.priority = priority;
+        pos
+    }
+
+    /// Lowers the priority of `handle`, in *O*(log n).
+    ///
+    /// # Panics
+    ///
+    /// If `handle` is not in the heap or if `priority` is greater than its
+    /// current one.
+    #[inline]
+    pub fn decrease_key(&mut self, handle: usize, priority: P) {
+        let pos = self.replace(handle, priority, Ordering::Less);
+        self.sift_up(pos);
+        self.debug_check()
+    }
+
+    /// Raises the priority of `handle`, in *O*(log n).
+    ///
+    /// # Panics
+    ///
+    /// If `handle` is not in the heap or if `priority` is less than its
+    /// current one.
+    #[inline]
+    pub fn increase_key(&mut self, handle: usize, priority: P) {
+        let pos = self.replace(handle, priority, Ordering::Greater);
+        self.sift_down(pos);
+        self.debug_check()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        for entry in self.heap.drain(..) {
+            self.index[entry.handle] = None
+        }
+        self.debug_check()
+    }
+}
+
+impl<P: Ord> Sift for IndexedHeap<P> {
+    #[inline]
+    fn higher(&self, a: usize, b: usize) -> bool {
+        self.heap[a].priority < self.heap[b].priority
+    }
+
+    #[inline]
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.update_index(a);
+        self.update_index(b)
+    }
+}
+
+impl<P: Ord> Default for IndexedHeap<P> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Ord + Debug> Debug for IndexedHeap<P> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let entries = self.heap.iter().map(|entry| (entry.handle, &entry.priority));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::XorShift;
+    use std::collections::BTreeSet;
+
+    fn is_valid<P: Ord>(heap: &IndexedHeap<P>) -> bool {
+        let parent = |pos: usize| &heap.heap[(pos - 1) / 2].priority;
+        let ordered = (1..heap.len()).all(|pos| *parent(pos) <= heap.heap[pos].priority);
+        let mut entries = heap.heap.iter().enumerate();
+        let indexed = entries.all(|(pos, entry)| heap.index[entry.handle] == Some(pos));
+        let count = heap.index.iter().filter(|pos| pos.is_some()).count();
+        ordered && indexed && count == heap.len()
+    }
+
+    #[test]
+    fn handles_and_priorities() {
+        let mut heap = IndexedHeap::new();
+        assert_eq!(heap.extract_min(), None::<(usize, u32)>);
+
+        assert_eq!(heap.insert(3, 30), None);
+        assert_eq!(heap.insert(7, 10), None);
+        assert_eq!(heap.insert(1, 20), None);
+        assert!(heap.contains(7) && !heap.contains(2) && !heap.contains(100));
+        assert_eq!((heap.priority(1), heap.peek_min()), (Some(&20), Some((7, &10))));
+
+        heap.decrease_key(3, 5);
+        heap.increase_key(7, 25);
+        assert_eq!(heap.insert(1, 15), Some(20));
+        assert!(is_valid(&heap));
+        assert_eq!(format!("{:?}", heap), "{3: 5, 7: 25, 1: 15}");
+
+        assert_eq!(heap.remove(1), Some(15));
+        assert_eq!(heap.remove(1), None);
+        assert_eq!(heap.extract_min(), Some((3, 5)));
+        assert_eq!(heap.extract_min(), Some((7, 25)));
+        assert!(heap.is_empty())
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong direction")]
+    fn decrease_to_greater() {
+        let mut heap = IndexedHeap::with_handles(4);
+        heap.insert(2, 'b');
+        heap.decrease_key(2, 'c')
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not indexed")]
+    fn corrupted_index() {
+        let mut heap = IndexedHeap::new();
+        heap.insert(0, 'a');
+        heap.insert(1, 'b');
+        heap.index.swap(0, 1);
+        assert_eq!(heap.insert(2, 'c'), None)
+    }
+
+    #[test]
+    fn random_operations() {
+        let mut rng = XorShift::new(12);
+        let mut heap = IndexedHeap::with_handles(64);
+        let mut model = BTreeSet::new();
+        let mut priorities = vec![None; 64];
+
+        for _ in 0..2000 {
+            let handle = rng.below(64);
+            let priority = rng.below(1000);
+            match (rng.below(4), priorities[handle]) {
+                (0, Some(old)) if priority <= old => heap.decrease_key(handle, priority),
+                (1, Some(old)) if priority >= old => heap.increase_key(handle, priority),
+                (2, old) => {
+                    assert_eq!(heap.remove(handle), old);
+                    if let Some(old) = priorities[handle].take() {
+                        model.remove(&(old, handle));
+                    }
+                    continue
+                },
+                (3, _) => {
+                    let min = model.iter().next().map(|&(priority, _)| priority);
+                    let extracted = heap.extract_min();
+                    assert_eq!(extracted.map(|(_, priority)| priority), min);
+                    if let Some((handle, priority)) = extracted {
+                        model.remove(&(priority, handle));
+                        priorities[handle] = None
+                    }
+                    continue
+                },
+                (_, old) => assert_eq!(heap.insert(handle, priority), old)
+            }
+            if let Some(old) = priorities[handle].replace(priority) {
+                model.remove(&(old, handle));
+            }
+            model.insert((priority, handle));
+            assert!(is_valid(&heap))
+        }
+    }
+
+    #[test]
+    fn dijkstra() {
+        let graph: &[&[(usize, u32)]] =
+            &[&[(1, 4), (2, 1)], &[(3, 1)], &[(1, 2), (3, 5)], &[(4, 3)], &[]];
+        let mut distance = vec![None; graph.len()];
+        let mut frontier = IndexedHeap::with_handles(graph.len());
+        frontier.insert(0, 0);
+
+        while let Some((vertex, dist)) = frontier.extract_min() {
+            distance[vertex] = Some(dist);
+            for &(next, weight) in graph[vertex] {
+                let candidate = dist + weight;
+                match frontier.priority(next) {
+                    _ if distance[next].is_some() => (),
+                    Some(&old) if old <= candidate => (),
+                    Some(_) => frontier.decrease_key(next, candidate),
+                    None => {
+                        frontier.insert(next, candidate);
+                    },
+                }
+            }
+        }
+        assert_eq!(distance, vec![Some(0), Some(3), Some(1), Some(4), Some(7)])
+    }
+}
@@ -0,0 +1,15 @@
+//! Priority queues.
+mod compare;
+mod sift;
+mod binary;
+mod indexed;
+mod priority_queue;
+mod dary;
+mod pairing;
+
+pub use compare::{by, Compare, FnCompare, Max, Min};
+pub use binary::{heapsort, heapsort_by, BinaryHeap, PeekMut};
+pub use indexed::IndexedHeap;
+pub use priority_queue::PriorityQueue;
+pub use dary::{DaryHandle, DaryHeap};
+pub use pairing::{PairingHandle, PairingHeap};
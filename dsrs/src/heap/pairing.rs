@@ -0,0 +1,417 @@
+use super::compare::{Compare, Min};
+use super::priority_queue::PriorityQueue;
+
+use mem::alloc::Layout;
+use mem::ptr::NonNull;
+
+use std::alloc::{handle_alloc_error, AllocRef, Global};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+use std::marker::PhantomData;
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    // leftmost child
+    child: Link<T>,
+    // next node with the same parent
+    sibling: Link<T>,
+    // parent for the leftmost child, previous sibling for the others
+    prev: Link<T>
+}
+
+impl<T> Node<T> {
+    #[inline]
+    fn new_in<A: AllocRef>(value: T, alloc: &mut A) -> NonNull<Self> {
+        let layout = Layout::new::<Self>();
+        let ptr: NonNull<Self> = match alloc.alloc(layout.inner()) {
+            Err(_) => handle_alloc_error(layout.inner()),
+            Ok(ptr) => NonNull::from_inner(ptr.cast())
+        };
+        // SAFETY: 'ptr' was just allocated for a 'Node<T>'
+        unsafe { ptr.as_ptr().write(Self { value, child: None, sibling: None, prev: None }) };
+        ptr
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must be a node allocated with `alloc` and not linked anywhere.
+    #[inline]
+    unsafe fn take_in<A: AllocRef>(ptr: NonNull<Self>, alloc: &mut A) -> T {
+        // SAFETY: caller guarantees that 'ptr' is a valid node, which
+        // is read only once and then released with the same allocator
+        unsafe {
+            let node = ptr.as_ptr().read();
+            alloc.dealloc(ptr.cast().inner(), Layout::new::<Self>().inner());
+            node.value
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must be a live node, not borrowed elsewhere.
+    #[inline]
+    unsafe fn get<'a>(ptr: NonNull<Self>) -> &'a mut Self {
+        // SAFETY: guaranteed by the caller
+        unsafe { &mut *ptr.as_ptr() }
+    }
+}
+
+/// Handle to a value in a [`PairingHeap`].
+pub struct PairingHandle<T>(NonNull<Node<T>>);
+
+impl<T> Clone for PairingHandle<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PairingHandle<T> {}
+
+impl<T> Debug for PairingHandle<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("PairingHandle").field(&self.0.as_ptr()).finish()
+    }
+}
+
+/// Priority queue over a multiway tree, with the greatest value according
+/// to `C` at the top, *O*(1) pushes and melds, and amortized *O*(log n)
+/// pops. It is a min-heap by default.
+///
+/// Each value lives in its own node, so handles stay valid after melding.
+pub struct PairingHeap<T, C: Compare<T> = Min, A: AllocRef = Global> {
+    root: Link<T>,
+    length: usize,
+    cmp: C,
+    alloc: A,
+    marker: PhantomData<Box<Node<T>>>
+}
+
+// SAFETY: the heap owns its nodes, just like a 'Box'
+unsafe impl<T: Send, C: Compare<T> + Send, A: AllocRef + Send> Send for PairingHeap<T, C, A> {}
+// SAFETY: shared references only give shared access to the nodes
+unsafe impl<T: Sync, C: Compare<T> + Sync, A: AllocRef + Sync> Sync for PairingHeap<T, C, A> {}
+
+/// Makes the root with the lower value the leftmost child of the other,
+/// returning the new root.
+///
+/// # Safety
+///
+/// Both must be different live roots, without siblings or parents.
+#[inline]
+unsafe fn link<T, C: Compare<T>>(
+    a: NonNull<Node<T>>,
+    b: NonNull<Node<T>>,
+    cmp: &C,
+) -> NonNull<Node<T>> {
+    // SAFETY: guaranteed by the caller
+    let higher = unsafe { cmp.compare(&b.as_ref().value, &a.as_ref().value) };
+    let (parent, child) = if higher == Ordering::Greater { (b, a) } else { (a, b) };
+    // SAFETY: same as above
+    let (parent_ref, child_ref) = unsafe { (Node::get(parent), Node::get(child)) };
+
+    if let Some(first) = parent_ref.child {
+        // SAFETY: children of a live node are live
+        unsafe { Node::get(first) }.prev = Some(child)
+    }
+    child_ref.sibling = parent_ref.child.replace(child);
+    child_ref.prev = Some(parent);
+    parent
+}
+
+/// Merges a list of siblings into a single root, first linking them in
+/// pairs from left to right, then linking the pairs from right to left.
+///
+/// # Safety
+///
+/// `first` must be the start of a list of live siblings, without parent.
+#[inline]
+unsafe fn merge_pairs<T, C: Compare<T>>(first: Link<T>, cmp: &C) -> Link<T> {
+    // pairs are kept in reverse order, linked by 'sibling'
+    let (mut pairs, mut next) = (None, first);
+    while let Some(a) = next {
+        // SAFETY: every node in the list is live
+        let a_ref = unsafe { Node::get(a) };
+        a_ref.prev = None;
+        let pair = match a_ref.sibling.take() {
+            None => {
+                next = None;
+                a
+            },
+            Some(b) => {
+                // SAFETY: same as above
+                let b_ref = unsafe { Node::get(b) };
+                next = b_ref.sibling.take();
+                b_ref.prev = None;
+                // SAFETY: both were detached above
+                unsafe { link(a, b, cmp) }
+            },
+        };
+        // SAFETY: 'pair' is a detached root
+        unsafe { Node::get(pair) }.sibling = pairs;
+        pairs = Some(pair)
+    }
+
+    let mut root = None;
+    while let Some(pair) = pairs {
+        // SAFETY: 'pair' is live and only linked by the pairs list
+        pairs = unsafe { Node::get(pair) }.sibling.take();
+        root = Some(match root {
+            None => pair,
+            // SAFETY: both are detached roots
+            Some(root) => unsafe { link(root, pair, cmp) }
+        })
+    }
+    root
+}
+
+impl<T: Ord> PairingHeap<T> {
+    /// Creates an empty min-heap.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T: Ord, A: AllocRef> PairingHeap<T, Min, A> {
+    /// Creates an empty min-heap, whose nodes will be allocated with
+    /// `alloc`.
+    #[must_use]
+    #[inline]
+    pub const fn new_in(alloc: A) -> Self {
+        Self::with_comparator_in(Min, alloc)
+    }
+}
+
+impl<T, C: Compare<T>> PairingHeap<T, C> {
+    #[must_use]
+    #[inline]
+    pub const fn with_comparator(cmp: C) -> Self {
+        Self::with_comparator_in(cmp, Global)
+    }
+
+    /// Moves every value of `other` into the heap, in *O*(1). Handles into
+    /// `other` are still valid in this heap.
+    #[inline]
+    pub fn meld(&mut self, mut other: Self) {
+        self.length += std::mem::replace(&mut other.length, 0);
+        self.root = match (self.root, other.root.take()) {
+            // SAFETY: both are roots of heaps with the global allocator
+            (Some(a), Some(b)) => Some(unsafe { link(a, b, &self.cmp) }),
+            (a, b) => a.or(b)
+        }
+    }
+}
+
+impl<T, C: Compare<T>, A: AllocRef> PairingHeap<T, C, A> {
+    /// Creates an empty heap ordered by `cmp`, whose nodes will be
+    /// allocated with `alloc`.
+    #[must_use]
+    #[inline]
+    pub const fn with_comparator_in(cmp: C, alloc: A) -> Self {
+        Self { root: None, length: 0, cmp, alloc, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Greatest value, in *O*(1).
+    #[must_use]
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        // SAFETY: 'root' is a node owned by the heap
+        self.root.map(|ptr| unsafe { &(*ptr.as_ptr()).value })
+    }
+
+    /// Inserts a value in *O*(1).
+    #[inline]
+    pub fn push(&mut self, value: T) -> PairingHandle<T> {
+        let node = Node::new_in(value, &mut self.alloc);
+        self.root = match self.root {
+            None => Some(node),
+            // SAFETY: 'node' was just allocated and 'root' is detached
+            Some(root) => Some(unsafe { link(root, node, &self.cmp) })
+        };
+        self.length += 1;
+        PairingHandle(node)
+    }
+
+    /// Removes the greatest value in amortized *O*(log n).
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        // SAFETY: the root is owned by the heap, and its children are
+        // detached from it before it is freed
+        unsafe {
+            self.root = merge_pairs(Node::get(root).child.take(), &self.cmp);
+            self.length -= 1;
+            Some(Node::take_in(root, &mut self.alloc))
+        }
+    }
+
+    /// Moves the value of `handle` up, in *O*(1), though it may make the
+    /// next pop slower.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been returned by [`push`](PairingHeap::push) on
+    /// this heap, or on one melded into it, and its value must still be in
+    /// the heap.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is less than the current value of `handle` according to
+    /// the comparator.
+    #[inline]
+    pub unsafe fn decrease_key(&mut self, handle: PairingHandle<T>, value: T) {
+        let node = handle.0;
+        // SAFETY: guaranteed by the caller
+        let node_ref = unsafe { Node::get(node) };
+        assert_ne!(
+            self.cmp.compare(&value, &node_ref.value),
+            Ordering::Less,
+            "the new value is lower in the heap than the current one"
+        );
+        node_ref.value = value;
+
+        let prev = match node_ref.prev.take() {
+            // already the root
+            None => return,
+            Some(prev) => prev
+        };
+        let sibling = node_ref.sibling.take();
+        // SAFETY: 'prev' and 'sibling' are live nodes in the same heap
+        unsafe {
+            let prev_ref = Node::get(prev);
+            if prev_ref.child == Some(node) {
+                prev_ref.child = sibling
+            } else {
+                prev_ref.sibling = sibling
+            }
+            if let Some(sibling) = sibling {
+                Node::get(sibling).prev = Some(prev)
+            }
+        }
+        let cmp = &self.cmp;
+        // SAFETY: 'node' was cut from its parent, and the heap is not
+        // empty, as 'node' is in it
+        self.root = self.root.map(|root| unsafe { link(root, node, cmp) })
+    }
+
+    /// Removes every value, in *O*(n).
+    #[inline]
+    pub fn clear(&mut self) {
+        self.length = 0;
+        // SAFETY: the nodes are no longer reachable from the heap
+        unsafe { free_all(self.root.take(), &mut self.alloc) }
+    }
+}
+
+/// Drops and releases every node in the tree of `root`, without recursion.
+///
+/// # Safety
+///
+/// `root` must be a detached tree allocated with `alloc`.
+#[inline]
+unsafe fn free_all<T, A: AllocRef>(root: Link<T>, alloc: &mut A) {
+    let mut next = root;
+    while let Some(ptr) = next {
+        // SAFETY: every node is only visited once, as the children are
+        // moved to the pending list before their parent is freed
+        unsafe {
+            let node = Node::get(ptr);
+            next = node.sibling.take();
+            if let Some(child) = node.child.take() {
+                let mut last = child;
+                while let Some(sibling) = Node::get(last).sibling {
+                    last = sibling
+                }
+                Node::get(last).sibling = next;
+                next = Some(child)
+            }
+            drop(Node::take_in(ptr, alloc))
+        }
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T, C> for PairingHeap<T, C> {
+    type Handle = PairingHandle<T>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn comparator(&self) -> &C {
+        self.comparator()
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    #[inline]
+    fn push(&mut self, value: T) -> PairingHandle<T> {
+        self.push(value)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    #[inline]
+    fn meld(&mut self, other: Self) {
+        self.meld(other)
+    }
+
+    #[inline]
+    unsafe fn decrease_key(&mut self, handle: PairingHandle<T>, value: T) {
+        // SAFETY: guaranteed by the caller
+        unsafe { self.decrease_key(handle, value) }
+    }
+}
+
+impl<T, C: Compare<T>, A: AllocRef> Drop for PairingHeap<T, C, A> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: the heap owns every node, and is not used after this
+        unsafe { free_all(self.root.take(), &mut self.alloc) }
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for PairingHeap<T, C> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T: Debug, C: Compare<T>, A: AllocRef> Debug for PairingHeap<T, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut debug = f.debug_struct("PairingHeap");
+        debug.field("len", &self.len()).field("top", &self.peek()).finish()
+    }
+}
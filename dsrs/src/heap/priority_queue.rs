@@ -0,0 +1,129 @@
+use super::compare::Compare;
+
+/// Priority queue with handles, so that queued values can be moved up.
+///
+/// The top of the queue is its greatest value according to `C`, so a
+/// min-queue is ordered by [`Min`](super::Min).
+pub trait PriorityQueue<T, C: Compare<T>> {
+    /// Reference to a queued value, valid until the value is popped.
+    type Handle: Copy;
+
+    fn len(&self) -> usize;
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn comparator(&self) -> &C;
+
+    /// Value at the top of the queue.
+    fn peek(&self) -> Option<&T>;
+
+    fn push(&mut self, value: T) -> Self::Handle;
+
+    /// Removes the value at the top of the queue.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Moves every value of `other` into this queue. Handles into `other`
+    /// should not be used afterwards, unless the implementation says so.
+    fn meld(&mut self, other: Self);
+
+    /// Replaces the value of `handle` with `value`, which must not be less
+    /// than the current one according to `C`. With [`Min`](super::Min),
+    /// this lowers the key of the value.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been returned by [`push`](PriorityQueue::push) on
+    /// this queue, and its value must still be queued.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is less than the current value according to `C`.
+    unsafe fn decrease_key(&mut self, handle: Self::Handle, value: T);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{BinaryHeap, DaryHeap, Max, Min, PairingHeap};
+    use super::*;
+    use crate::random::XorShift;
+
+    fn sorts<Q: PriorityQueue<usize, Min> + Default>() {
+        let mut rng = XorShift::new(5);
+        let mut queue = Q::default();
+        let mut values: Vec<_> = (0..300).map(|_| rng.below(100)).collect();
+        for &value in &values {
+            queue.push(value);
+        }
+        assert_eq!((queue.len(), queue.peek()), (300, values.iter().min()));
+
+        values.sort_unstable();
+        let popped: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(popped, values);
+        assert!(queue.is_empty())
+    }
+
+    fn decreases<Q: PriorityQueue<usize, Min> + Default>() {
+        let mut queue = Q::default();
+        let handles: Vec<_> = (0..10).map(|value| queue.push(100 + 10 * value)).collect();
+        // SAFETY: nothing was popped yet
+        unsafe {
+            queue.decrease_key(handles[5], 5);
+            queue.decrease_key(handles[9], 105);
+            queue.decrease_key(handles[0], 100)
+        }
+        assert_eq!(queue.pop(), Some(5));
+
+        // SAFETY: only the value of 'handles[5]' was popped
+        unsafe { queue.decrease_key(handles[8], 1) };
+        let popped: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(popped, vec![1, 100, 105, 110, 120, 130, 140, 160, 170])
+    }
+
+    fn melds<Q: PriorityQueue<usize, Min> + Default>() {
+        let (mut even, mut odd) = (Q::default(), Q::default());
+        for value in 0..20 {
+            let queue = if value % 2 == 0 { &mut even } else { &mut odd };
+            queue.push(value);
+        }
+        even.meld(odd);
+        even.meld(Q::default());
+        assert_eq!(even.len(), 20);
+        assert!(std::iter::from_fn(|| even.pop()).eq(0..20))
+    }
+
+    fn raises<Q: PriorityQueue<usize, Max> + Default>() {
+        let mut queue = Q::default();
+        let handles: Vec<_> = (0..10).map(|value| queue.push(value)).collect();
+        // SAFETY: nothing was popped yet
+        unsafe { queue.decrease_key(handles[3], 20) };
+        assert_eq!((queue.pop(), queue.pop(), queue.len()), (Some(20), Some(9), 8))
+    }
+
+    fn check_all<Q: PriorityQueue<usize, Min> + Default>() {
+        sorts::<Q>();
+        decreases::<Q>();
+        melds::<Q>()
+    }
+
+    #[test]
+    fn binary_heap() {
+        check_all::<BinaryHeap<_, Min>>();
+        raises::<BinaryHeap<_>>()
+    }
+
+    #[test]
+    fn dary_heap() {
+        check_all::<DaryHeap<_, Min, 3>>();
+        check_all::<DaryHeap<_, Min, 8>>();
+        raises::<DaryHeap<_, Max, 4>>()
+    }
+
+    #[test]
+    fn pairing_heap() {
+        check_all::<PairingHeap<_>>();
+        raises::<PairingHeap<_, Max>>()
+    }
+}
@@ -0,0 +1,102 @@
+use super::compare::Compare;
+
+use std::cmp::Ordering;
+
+/// Values of a heap over an implicit tree, seen through their positions.
+pub(super) trait Sift {
+    /// Whether the value at `a` belongs above the one at `b`.
+    fn higher(&self, a: usize, b: usize) -> bool;
+
+    /// Swaps two values, along with whatever tracks their positions.
+    fn swap(&mut self, a: usize, b: usize);
+}
+
+/// Slice ordered by a comparator, with no positions to track.
+pub(super) struct Ordered<'a, T, C> {
+    pub(super) data: &'a mut [T],
+    pub(super) cmp: &'a C
+}
+
+impl<T, C: Compare<T>> Sift for Ordered<'_, T, C> {
+    #[inline]
+    fn higher(&self, a: usize, b: usize) -> bool {
+        self.cmp.compare(&self.data[a], &self.data[b]) == Ordering::Greater
+    }
+
+    #[inline]
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b)
+    }
+}
+
+/// Moves the value at `pos` up while it is higher than its parent, in a
+/// tree where each node has `arity` children. Returns its final position.
+#[inline]
+pub(super) fn sift_up<H: Sift>(heap: &mut H, mut pos: usize, arity: usize) -> usize {
+    while pos > 0 {
+        let parent = (pos - 1) / arity;
+        if !heap.higher(pos, parent) {
+            break
+        }
+        heap.swap(pos, parent);
+        pos = parent
+    }
+    pos
+}
+
+/// Moves the value at `pos` down while one of its children is higher,
+/// looking only at the first `len` values. Returns its final position.
+#[inline]
+pub(super) fn sift_down<H: Sift>(heap: &mut H, mut pos: usize, len: usize, arity: usize) -> usize {
+    loop {
+        let first = arity * pos + 1;
+        if first >= len {
+            return pos
+        }
+        let mut child = first;
+        for next in first + 1..len.min(first + arity) {
+            if heap.higher(next, child) {
+                child = next
+            }
+        }
+        if !heap.higher(child, pos) {
+            return pos
+        }
+        heap.swap(pos, child);
+        pos = child
+    }
+}
+
+/// Reorders the first `len` values as a heap, in *O*(n).
+#[inline]
+pub(super) fn heapify<H: Sift>(heap: &mut H, len: usize, arity: usize) {
+    // only the values with children can move down
+    for pos in (0..(len + arity - 2) / arity).rev() {
+        sift_down(heap, pos, len, arity);
+    }
+}
+
+/// Sorts the first `len` values of a heap in ascending order, moving the
+/// top to the end one at a time, in *O*(n log n).
+#[inline]
+pub(super) fn sort<H: Sift>(heap: &mut H, len: usize, arity: usize) {
+    for end in (1..len).rev() {
+        heap.swap(0, end);
+        sift_down(heap, 0, end, arity);
+    }
+}
+
+/// Restores the heap order of the first `len` values, after the ones from
+/// `start` on were appended to a heap.
+#[inline]
+pub(super) fn sift_appended<H: Sift>(heap: &mut H, start: usize, len: usize, arity: usize) {
+    // rebuilding is linear, while each sift up is logarithmic
+    let log = len.next_power_of_two().trailing_zeros() as usize;
+    if (len - start) * log > len {
+        heapify(heap, len, arity)
+    } else {
+        for pos in start..len {
+            sift_up(heap, pos, arity);
+        }
+    }
+}
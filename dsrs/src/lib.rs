@@ -1,11 +1,23 @@
+#![feature(allocator_api)]
 #![feature(unsafe_block_in_unsafe_fn)]
 #![feature(const_fn)]
 #![feature(const_mut_refs)]
 #![feature(const_option)]
+#![feature(const_panic)]
 #![deny(unsafe_op_in_unsafe_fn)]
+// waiting on https://github.com/rust-lang/rust/issues/75913
+#![allow(incomplete_features)]
+#![feature(const_generics)]
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(clippy::cargo_common_metadata)]
 #![allow(clippy::multiple_crate_versions)]
 #![allow(clippy::wildcard_dependencies)]
 #![allow(clippy::module_name_repetitions)]
+pub mod deque;
+pub mod heap;
 pub mod lists;
+pub mod queue;
+pub mod random;
+pub mod stack;
+pub mod tree;
+pub mod vec;
@@ -0,0 +1,379 @@
+use super::linked_list::node::{Link, Node};
+use super::LinkedList;
+
+use mem::ptr::NonNull;
+
+use std::alloc::Global;
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FusedIterator;
+
+/// Singly linked ring, which only keeps a pointer to its last node.
+///
+/// The nodes have the same layout as the ones in [`LinkedList`], with the
+/// tail linking to the head. So moving the tail around the ring is enough
+/// to rotate the list.
+pub struct CircularList<T: ?Sized> {
+    tail: Link<T>,
+    length: usize
+}
+
+// SAFETY: the ring owns its nodes, just like a 'Box'
+unsafe impl<T: ?Sized + Send> Send for CircularList<T> {}
+// SAFETY: shared references only give shared access to the nodes
+unsafe impl<T: ?Sized + Sync> Sync for CircularList<T> {}
+
+impl<T: ?Sized> CircularList<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { tail: None, length: 0 }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.tail.is_none()
+    }
+
+    #[inline]
+    fn head(&self) -> Option<&Node<T>> {
+        // SAFETY: 'tail' is owned by the ring, and its 'next' is never
+        // 'None' while in the ring
+        self.tail.and_then(|ptr| unsafe { &*ptr.as_ptr() }.next_ref())
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.head().map(|node| &node.data)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: 'tail' is owned by the ring
+        self.tail.map(|ptr| unsafe { &(*ptr.as_ptr()).data })
+    }
+
+    /// Links `node` between the tail and the head of the ring.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be allocated with [`Global`] and `node.next` must
+    /// be `None`.
+    #[inline]
+    unsafe fn link_node(&mut self, node: NonNull<Node<T>>) -> NonNull<Node<T>> {
+        // SAFETY: caller ensures that 'node' is valid
+        let new = unsafe { &mut *node.as_ptr() };
+        debug_assert!(new.next.is_none());
+
+        match self.tail {
+            // single node points to itself
+            None => {
+                new.next = Some(node);
+                self.tail = Some(node)
+            },
+            Some(tail) => {
+                // SAFETY: 'tail' is owned by the ring
+                let tail = unsafe { &mut *tail.as_ptr() };
+                new.next = tail.next.replace(node)
+            },
+        }
+        self.length += 1;
+        node
+    }
+
+    /// Removes the node after `prev` from the ring, whose ownership goes
+    /// to the caller.
+    ///
+    /// # Safety
+    ///
+    /// `prev` must be a node owned by the ring.
+    #[inline]
+    unsafe fn unlink_after(&mut self, prev: NonNull<Node<T>>) -> NonNull<Node<T>> {
+        // SAFETY: 'prev' is owned by the ring
+        let prev_node = unsafe { &mut *prev.as_ptr() };
+        let node = match prev_node.next.take() {
+            Some(node) => node,
+            None => unreachable!("nodes in a ring always have a next node")
+        };
+
+        if self.length == 1 {
+            // 'node' is 'prev' itself
+            self.tail = None
+        } else {
+            // SAFETY: 'node' is owned by the ring
+            prev_node.next = unsafe { &mut *node.as_ptr() }.next.take();
+            if self.tail == Some(node) {
+                self.tail = Some(prev)
+            }
+        }
+        self.length -= 1;
+        node
+    }
+
+    #[inline]
+    fn pop_node(&mut self) -> Link<T> {
+        // SAFETY: 'tail' is owned by the ring
+        self.tail.map(|tail| unsafe { self.unlink_after(tail) })
+    }
+
+    /// Moves the front element to the back, `n` times.
+    ///
+    /// Only walks `n % len` nodes.
+    #[inline]
+    pub fn rotate(&mut self, n: usize) {
+        if let Some(mut tail) = self.tail {
+            for _ in 0..n % self.length {
+                // SAFETY: 'tail' is owned by the ring
+                tail = match unsafe { tail.as_ref() }.next {
+                    Some(next) => next,
+                    None => unreachable!("nodes in a ring always have a next node")
+                }
+            }
+            self.tail = Some(tail)
+        }
+    }
+
+    /// Removes every `k`-th element around the ring, until it is empty, as
+    /// in the Josephus problem. The count starts from the front, which is
+    /// the first element.
+    ///
+    /// Returns the removed elements in the order they were removed, so the
+    /// survivor is the last one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    #[must_use]
+    #[inline]
+    pub fn remove_every_kth(&mut self, k: usize) -> LinkedList<T> {
+        assert!(k > 0, "cannot remove every 0th element");
+
+        let mut removed = LinkedList::new();
+        while !self.is_empty() {
+            self.rotate(k - 1);
+            if let Some(node) = self.pop_node() {
+                // SAFETY: both use the global allocator and 'node.next'
+                // was already taken from the ring
+                unsafe { removed.push_node_tail(node) }
+            }
+        }
+        removed
+    }
+
+    #[inline]
+    pub fn push_front_boxed(&mut self, data: Box<T>) {
+        let node = Node::build_in(data, None, &mut Global);
+        // SAFETY: allocated with 'Global' and next is None
+        unsafe { self.link_node(node) };
+    }
+
+    #[inline]
+    pub fn push_back_boxed(&mut self, data: Box<T>) {
+        let node = Node::build_in(data, None, &mut Global);
+        // SAFETY: allocated with 'Global' and next is None
+        let ptr = unsafe { self.link_node(node) };
+        self.tail = Some(ptr)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop_front_boxed(&mut self) -> Option<Box<T>> {
+        let node = self.pop_node()?;
+        // SAFETY: the node was unlinked and allocated with 'Global'
+        Some(unsafe { Node::split_in(node, &mut Global) }.0)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head(), length: self.length }
+    }
+}
+
+impl<T> CircularList<T> {
+    #[inline]
+    pub fn push_front(&mut self, data: T) {
+        let node = Node::new_in(data, None, &mut Global);
+        // SAFETY: allocated with 'Global' and next is None
+        unsafe { self.link_node(node) };
+    }
+
+    #[inline]
+    pub fn push_back(&mut self, data: T) {
+        let node = Node::new_in(data, None, &mut Global);
+        // SAFETY: allocated with 'Global' and next is None
+        let ptr = unsafe { self.link_node(node) };
+        self.tail = Some(ptr)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.pop_node()?;
+        // SAFETY: the node was unlinked and allocated with 'Global'
+        Some(unsafe { Node::take_in(node, &mut Global) }.0)
+    }
+}
+
+impl<T: ?Sized> Drop for CircularList<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // break the ring at the tail, then free
+        // the nodes one at a time
+        let mut next = self.tail.take().and_then(|tail| {
+            // SAFETY: 'tail' is owned by the ring
+            unsafe { &mut *tail.as_ptr() }.next.take()
+        });
+        while let Some(ptr) = next {
+            // SAFETY: each node is visited only once, after the ring
+            // was broken
+            next = unsafe { &mut *ptr.as_ptr() }.next.take();
+            // SAFETY: the node was unlinked and allocated with 'Global'
+            unsafe { Node::free_in(ptr, &mut Global) }
+        }
+    }
+}
+
+impl<T: ?Sized> Default for CircularList<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for CircularList<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Borrowing iterator over the elements of a [`CircularList`], going
+/// around the ring only once.
+///
+/// Created by [`CircularList::iter`].
+pub struct Iter<'a, T: ?Sized> {
+    next: Option<&'a Node<T>>,
+    length: usize
+}
+
+// SAFETY: behaves like a '&T'
+unsafe impl<T: ?Sized + Sync> Send for Iter<'_, T> {}
+// SAFETY: behaves like a '&T'
+unsafe impl<T: ?Sized + Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.length == 0 {
+            return None
+        }
+        self.next.map(|node| {
+            self.next = node.next_ref();
+            self.length -= 1;
+            &node.data
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T: ?Sized> IntoIterator for &'a CircularList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents<T: Copy>(list: &CircularList<T>) -> Vec<T> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn push_pop_and_rotate() {
+        let mut ring = CircularList::new();
+        ring.push_back(2);
+        ring.push_front(1);
+        ring.push_back(3);
+        assert_eq!(contents(&ring), vec![1, 2, 3]);
+        assert_eq!((ring.front(), ring.back()), (Some(&1), Some(&3)));
+
+        ring.rotate(4);
+        assert_eq!(contents(&ring), vec![2, 3, 1]);
+        ring.rotate(0);
+        ring.rotate(2);
+        assert_eq!(contents(&ring), vec![1, 2, 3]);
+
+        assert_eq!(ring.pop_front(), Some(1));
+        assert_eq!(ring.pop_front(), Some(2));
+        assert_eq!((ring.front(), ring.back(), ring.len()), (Some(&3), Some(&3), 1));
+        ring.rotate(5);
+        assert_eq!(ring.pop_front(), Some(3));
+        assert_eq!(ring.pop_front(), None);
+        assert!(ring.is_empty())
+    }
+
+    #[test]
+    fn josephus() {
+        let mut ring = CircularList::new();
+        for i in 1..=7 {
+            ring.push_back(i)
+        }
+        let order: Vec<_> = ring.remove_every_kth(3).into_iter().collect();
+        assert_eq!(order, vec![3, 6, 2, 7, 5, 1, 4]);
+        assert!(ring.is_empty());
+
+        for i in 1..=4 {
+            ring.push_back(i)
+        }
+        let order: Vec<_> = ring.remove_every_kth(1).into_iter().collect();
+        assert_eq!(order, vec![1, 2, 3, 4])
+    }
+
+    #[test]
+    fn unsized_ring() {
+        let mut ring = CircularList::<str>::new();
+        for name in &["ana", "bia", "caio", "duda"] {
+            ring.push_back_boxed((*name).into())
+        }
+        ring.rotate(1);
+        assert_eq!(ring.pop_front_boxed().as_deref(), Some("bia"));
+
+        let order = ring.remove_every_kth(2);
+        assert_eq!(order.iter().collect::<Vec<_>>(), vec!["duda", "caio", "ana"]);
+        assert_eq!(order.tail(), Some("ana"))
+    }
+
+    #[test]
+    fn drop_long_ring() {
+        let mut ring = CircularList::new();
+        for i in 0..1_000_000_u32 {
+            ring.push_front(i)
+        }
+        drop(ring)
+    }
+}
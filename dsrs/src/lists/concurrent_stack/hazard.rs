@@ -0,0 +1,186 @@
+//! Hazard pointers, for memory reclamation in lock-free structures.
+//!
+//! A thread announces the pointer it is about to read in its [`Slot`].
+//! Removed allocations are only retired, and are freed later, after a
+//! scan shows that no slot holds them anymore.
+use mem::alloc::Layout;
+use mem::ptr::NonNull;
+
+use std::alloc::{AllocRef, Global};
+use std::cell::UnsafeCell;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+/// Number of retired allocations that a slot keeps before scanning.
+const SCAN_THRESHOLD: usize = 64;
+
+/// Hazard pointer record, owned by a single thread at a time.
+struct Slot<T> {
+    hazard: AtomicPtr<T>,
+    in_use: AtomicBool,
+    // only accessed by the thread that owns the slot
+    retired: UnsafeCell<Vec<NonNull<T>>>,
+    // slots are never removed, so this is fixed after publishing
+    next: *mut Self
+}
+
+/// Set of hazard pointers that protect allocations of `T`.
+///
+/// Retired allocations are released with [`Global`], without dropping,
+/// so their contents must be moved out before retiring.
+pub(super) struct Domain<T> {
+    slots: AtomicPtr<Slot<T>>
+}
+
+impl<T> Domain<T> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self { slots: AtomicPtr::new(ptr::null_mut()) }
+    }
+
+    /// Takes a free slot, or publishes a new one if every slot is taken.
+    #[inline]
+    pub fn acquire(&self) -> Guard<'_, T> {
+        let mut current = self.slots.load(Ordering::Acquire);
+        while !current.is_null() {
+            // SAFETY: slots are only released when the domain is dropped
+            let slot = unsafe { &*current };
+            let taken = slot.in_use.load(Ordering::Relaxed)
+                || slot
+                    .in_use
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_err();
+            if !taken {
+                return Guard { slot, domain: self }
+            }
+            current = slot.next
+        }
+
+        let slot = Box::into_raw(Box::new(Slot {
+            hazard: AtomicPtr::new(ptr::null_mut()),
+            in_use: AtomicBool::new(true),
+            retired: UnsafeCell::new(Vec::new()),
+            next: ptr::null_mut()
+        }));
+        let mut head = self.slots.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: 'slot' is not published yet
+            unsafe { (*slot).next = head };
+            let swapped =
+                self.slots.compare_exchange_weak(head, slot, Ordering::Release, Ordering::Relaxed);
+            match swapped {
+                Ok(_) => break,
+                Err(actual) => head = actual
+            }
+        }
+        // SAFETY: just published, and only released with the domain
+        Guard { slot: unsafe { &*slot }, domain: self }
+    }
+
+    /// Releases every retired allocation in `retired` that is not
+    /// protected by any slot.
+    #[inline]
+    fn scan(&self, retired: &mut Vec<NonNull<T>>) {
+        let mut hazards = Vec::new();
+        let mut current = self.slots.load(Ordering::Acquire);
+        while !current.is_null() {
+            // SAFETY: slots are only released when the domain is dropped
+            let slot = unsafe { &*current };
+            let hazard = slot.hazard.load(Ordering::SeqCst);
+            if !hazard.is_null() {
+                hazards.push(hazard)
+            }
+            current = slot.next
+        }
+
+        retired.retain(|&ptr| {
+            let protected = hazards.contains(&ptr.as_ptr());
+            if !protected {
+                // SAFETY: retired and not protected, so no one else
+                // can access it anymore
+                unsafe { release(ptr) }
+            }
+            protected
+        })
+    }
+}
+
+impl<T> Drop for Domain<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let mut current = *self.slots.get_mut();
+        while !current.is_null() {
+            // SAFETY: no guards can outlive the domain, so every slot
+            // and retired allocation is unused
+            let Slot { retired, next, .. } = *unsafe { Box::from_raw(current) };
+            for ptr in retired.into_inner() {
+                // SAFETY: see above
+                unsafe { release(ptr) }
+            }
+            current = next
+        }
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must be a [`Global`] allocation for a `T`, whose contents were
+/// already moved out.
+#[inline]
+unsafe fn release<T>(ptr: NonNull<T>) {
+    // SAFETY: guaranteed by the caller
+    unsafe { Global.dealloc(ptr.cast().inner(), Layout::new::<T>().inner()) }
+}
+
+/// Exclusive access to a slot of a [`Domain`], which is freed on drop.
+pub(super) struct Guard<'a, T> {
+    slot: &'a Slot<T>,
+    domain: &'a Domain<T>
+}
+
+impl<T> Guard<'_, T> {
+    /// Loads `src` and protects the loaded pointer, so that it can't be
+    /// released until the protection changes.
+    #[inline]
+    pub fn protect(&self, src: &AtomicPtr<T>) -> *mut T {
+        let mut ptr = src.load(Ordering::Acquire);
+        loop {
+            self.slot.hazard.store(ptr, Ordering::SeqCst);
+            // check again, after the hazard is visible
+            let current = src.load(Ordering::SeqCst);
+            if current == ptr {
+                break ptr
+            }
+            ptr = current
+        }
+    }
+
+    #[inline]
+    pub fn clear(&self) {
+        self.slot.hazard.store(ptr::null_mut(), Ordering::Release)
+    }
+
+    /// Schedules `ptr` to be released once no slot protects it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a [`Global`] allocation for a `T`, whose contents were
+    /// already moved out, and it must not be reachable for new readers.
+    #[inline]
+    pub unsafe fn retire(&self, ptr: NonNull<T>) {
+        // SAFETY: the slot is owned by this guard
+        let retired = unsafe { &mut *self.slot.retired.get() };
+        retired.push(ptr);
+        if retired.len() >= SCAN_THRESHOLD {
+            self.domain.scan(retired)
+        }
+    }
+}
+
+impl<T> Drop for Guard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+        self.slot.in_use.store(false, Ordering::Release)
+    }
+}
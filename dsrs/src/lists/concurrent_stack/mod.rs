@@ -0,0 +1,4 @@
+mod hazard;
+mod stack;
+
+pub use stack::ConcurrentStack;
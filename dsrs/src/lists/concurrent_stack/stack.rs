@@ -0,0 +1,218 @@
+use super::hazard::Domain;
+use crate::lists::linked_list::node::Node;
+use crate::lists::LinkedList;
+
+use mem::ptr::NonNull;
+
+use std::alloc::Global;
+use std::fmt::{Debug, Formatter, Result};
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// Lock-free LIFO stack, that can be shared between threads.
+///
+/// This is a Treiber stack, with the same nodes as [`LinkedList`] and an
+/// atomic head. Popped nodes are reclaimed with hazard pointers, so a
+/// node is never released while another thread is still reading it.
+pub struct ConcurrentStack<T> {
+    head: AtomicPtr<Node<T>>,
+    domain: Domain<Node<T>>,
+    marker: PhantomData<Box<Node<T>>>
+}
+
+// SAFETY: the stack owns its values, and they are only moved
+// in and out, never shared between threads
+unsafe impl<T: Send> Send for ConcurrentStack<T> {}
+// SAFETY: same as above, no '&T' is ever handed out
+unsafe impl<T: Send> Sync for ConcurrentStack<T> {}
+
+impl<T> ConcurrentStack<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { head: AtomicPtr::new(ptr::null_mut()), domain: Domain::new(), marker: PhantomData }
+    }
+
+    /// Whether the stack was empty at the moment of the check.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    #[inline]
+    pub fn push(&self, data: T) {
+        let node = Node::new_in(data, None, &mut Global);
+
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: 'node' is not published yet
+            unsafe { (*node.as_ptr()).next = NonNull::new(head) };
+            let swapped = self.head.compare_exchange_weak(
+                head,
+                node.as_ptr(),
+                Ordering::Release,
+                Ordering::Relaxed,
+            );
+            match swapped {
+                Ok(_) => break,
+                Err(actual) => head = actual
+            }
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        let guard = self.domain.acquire();
+        loop {
+            let head = NonNull::new(guard.protect(&self.head))?;
+            // SAFETY: 'head' is protected, so it can't be released, and
+            // 'next' is never changed after the node is published
+            let next = unsafe { head.as_ref() }.next.map_or(ptr::null_mut(), NonNull::as_ptr);
+
+            let current = head.as_ptr();
+            let swapped =
+                self.head.compare_exchange(current, next, Ordering::SeqCst, Ordering::Relaxed);
+            if swapped.is_ok() {
+                guard.clear();
+                // SAFETY: the node was unlinked by this thread, so its data
+                // is moved out only once, and then the node is retired
+                unsafe {
+                    let data = ptr::read(&(*head.as_ptr()).data);
+                    guard.retire(head);
+                    return Some(data)
+                }
+            }
+        }
+    }
+
+    /// Takes every element currently in the stack, in popping order.
+    ///
+    /// The whole stack is detached at once, so concurrent pushes either
+    /// happen before, and are drained, or after and are kept.
+    #[must_use]
+    #[inline]
+    pub fn drain(&self) -> LinkedList<T> {
+        let mut list = LinkedList::new();
+        let mut next = NonNull::new(self.head.swap(ptr::null_mut(), Ordering::SeqCst));
+        if next.is_none() {
+            return list
+        }
+
+        // other threads might still be reading these nodes, so they
+        // must be retired too
+        let guard = self.domain.acquire();
+        while let Some(node) = next {
+            // SAFETY: the chain was detached by this thread, so every node
+            // is moved out only once, and its 'next' is unchanged
+            unsafe {
+                next = node.as_ref().next;
+                list.push_tail(ptr::read(&(*node.as_ptr()).data));
+                guard.retire(node)
+            }
+        }
+        list
+    }
+}
+
+impl<T> Drop for ConcurrentStack<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let mut next = NonNull::new(*self.head.get_mut());
+        while let Some(node) = next {
+            // SAFETY: no other thread has access to the stack anymore,
+            // and each node is visited only once
+            next = unsafe { Node::take_in(node, &mut Global) }.1
+        }
+    }
+}
+
+impl<T> Default for ConcurrentStack<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for ConcurrentStack<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ConcurrentStack").field("is_empty", &self.is_empty()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn push_pop_and_drain() {
+        let stack = ConcurrentStack::new();
+        assert_eq!(stack.pop(), None);
+        for i in 0..5 {
+            stack.push(i)
+        }
+        assert_eq!((stack.pop(), stack.pop()), (Some(4), Some(3)));
+
+        let drained = stack.drain();
+        assert_eq!(drained.into_iter().collect::<Vec<_>>(), vec![2, 1, 0]);
+        assert!(stack.is_empty());
+        assert!(stack.drain().is_empty())
+    }
+
+    #[test]
+    fn drops_every_value_once() {
+        let value = Arc::new(());
+        let stack = ConcurrentStack::new();
+        for _ in 0..1000 {
+            stack.push(Arc::clone(&value))
+        }
+        for _ in 0..300 {
+            let _ = stack.pop();
+        }
+        assert_eq!(Arc::strong_count(&value), 701);
+        assert_eq!(stack.drain().into_iter().take(100).count(), 100);
+        for _ in 0..200 {
+            stack.push(Arc::clone(&value))
+        }
+        drop(stack);
+        assert_eq!(Arc::strong_count(&value), 1)
+    }
+
+    #[test]
+    fn stress_many_threads() {
+        const THREADS: usize = 8;
+        const PUSHES: usize = 20_000;
+
+        let stack = Arc::new(ConcurrentStack::new());
+        let mut workers = Vec::new();
+        for id in 0..THREADS {
+            let stack = Arc::clone(&stack);
+            workers.push(thread::spawn(move || {
+                let mut popped = Vec::new();
+                for i in 0..PUSHES {
+                    stack.push(id * PUSHES + i);
+                    if i % 3 != 0 {
+                        popped.extend(stack.pop())
+                    }
+                    if i % 1000 == 0 {
+                        popped.extend(stack.drain())
+                    }
+                }
+                popped
+            }))
+        }
+
+        let mut seen = Vec::new();
+        for worker in workers {
+            seen.extend(worker.join().unwrap())
+        }
+        seen.extend(stack.drain());
+        seen.sort_unstable();
+        assert_eq!(seen, (0..THREADS * PUSHES).collect::<Vec<_>>())
+    }
+}
@@ -0,0 +1,390 @@
+use super::list::DoublyLinkedList;
+use super::node::{Link, Node};
+
+/// Read-only bidirectional cursor over a [`DoublyLinkedList`].
+///
+/// A cursor points either to an element of the list or to a "ghost"
+/// position, between the tail and the head.
+///
+/// Created by [`DoublyLinkedList::cursor_front`] or
+/// [`DoublyLinkedList::cursor_back`].
+#[derive(Debug)]
+pub struct Cursor<'a, T: ?Sized> {
+    current: Link<T>,
+    index: usize,
+    list: &'a DoublyLinkedList<T>
+}
+
+impl<T: ?Sized> Clone for Cursor<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { current: self.current, index: self.index, list: self.list }
+    }
+}
+
+impl<'a, T: ?Sized> Cursor<'a, T> {
+    /// Position of the current element, or `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn current(&self) -> Option<&'a T> {
+        // SAFETY: 'current' is a node owned by the borrowed list
+        self.current.map(|ptr| unsafe { &(*ptr.as_ptr()).data })
+    }
+
+    /// Element after the current one. At the ghost position, this is the
+    /// head of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = match self.current {
+            None => self.list.head,
+            // SAFETY: 'current' is a node owned by the borrowed list
+            Some(ptr) => unsafe { ptr.as_ref() }.next
+        };
+        // SAFETY: 'next' is a node owned by the borrowed list
+        next.map(|ptr| unsafe { &(*ptr.as_ptr()).data })
+    }
+
+    /// Element before the current one. At the ghost position, this is the
+    /// tail of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let prev = match self.current {
+            None => self.list.tail,
+            // SAFETY: 'current' is a node owned by the borrowed list
+            Some(ptr) => unsafe { ptr.as_ref() }.prev
+        };
+        // SAFETY: 'prev' is a node owned by the borrowed list
+        prev.map(|ptr| unsafe { &(*ptr.as_ptr()).data })
+    }
+
+    /// Moves to the next element, going through the ghost position after
+    /// the tail.
+    #[inline]
+    pub fn move_next(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.head;
+                self.index = 0
+            },
+            Some(ptr) => {
+                // SAFETY: 'current' is a node owned by the borrowed list
+                self.current = unsafe { ptr.as_ref() }.next;
+                self.index += 1
+            },
+        }
+    }
+
+    /// Moves to the previous element, going through the ghost position
+    /// before the head.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len().wrapping_sub(1)
+            },
+            Some(ptr) => {
+                // SAFETY: 'current' is a node owned by the borrowed list
+                self.current = unsafe { ptr.as_ref() }.prev;
+                self.index = self.index.wrapping_sub(1)
+            },
+        }
+    }
+}
+
+/// Bidirectional cursor over a [`DoublyLinkedList`] with editing
+/// operations.
+///
+/// Just like [`Cursor`], this may point to a "ghost" position, between the
+/// tail and the head.
+///
+/// Created by [`DoublyLinkedList::cursor_front_mut`] or
+/// [`DoublyLinkedList::cursor_back_mut`].
+#[derive(Debug)]
+pub struct CursorMut<'a, T: ?Sized> {
+    current: Link<T>,
+    index: usize,
+    list: &'a mut DoublyLinkedList<T>
+}
+
+impl<T: ?Sized> CursorMut<'_, T> {
+    /// Position of the current element, or `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: 'current' is a node owned by the list, which is
+        // mutably borrowed by the cursor
+        self.current.map(|ptr| unsafe { &mut (*ptr.as_ptr()).data })
+    }
+
+    /// Element after the current one. At the ghost position, this is the
+    /// head of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            None => self.list.head,
+            // SAFETY: 'current' is a node owned by the list
+            Some(ptr) => unsafe { ptr.as_ref() }.next
+        };
+        // SAFETY: 'next' is a node owned by the list
+        next.map(|ptr| unsafe { &mut (*ptr.as_ptr()).data })
+    }
+
+    /// Element before the current one. At the ghost position, this is the
+    /// tail of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            None => self.list.tail,
+            // SAFETY: 'current' is a node owned by the list
+            Some(ptr) => unsafe { ptr.as_ref() }.prev
+        };
+        // SAFETY: 'prev' is a node owned by the list
+        prev.map(|ptr| unsafe { &mut (*ptr.as_ptr()).data })
+    }
+
+    /// Moves to the next element, going through the ghost position after
+    /// the tail.
+    #[inline]
+    pub fn move_next(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.head;
+                self.index = 0
+            },
+            Some(ptr) => {
+                // SAFETY: 'current' is a node owned by the list
+                self.current = unsafe { ptr.as_ref() }.next;
+                self.index += 1
+            },
+        }
+    }
+
+    /// Moves to the previous element, going through the ghost position
+    /// before the head.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len().wrapping_sub(1)
+            },
+            Some(ptr) => {
+                // SAFETY: 'current' is a node owned by the list
+                self.current = unsafe { ptr.as_ref() }.prev;
+                self.index = self.index.wrapping_sub(1)
+            },
+        }
+    }
+
+    /// Read-only cursor at the same position.
+    #[must_use]
+    #[inline]
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor { current: self.current, index: self.index, list: self.list }
+    }
+
+    #[inline]
+    fn insert_node_after(&mut self, node: Box<Node<T>>) {
+        // SAFETY: 'current' is owned by the list
+        unsafe { self.list.link_after(self.current, node) }
+    }
+
+    #[inline]
+    fn insert_node_before(&mut self, node: Box<Node<T>>) {
+        let prev = match self.current {
+            None => self.list.tail,
+            Some(ptr) => {
+                self.index += 1;
+                // SAFETY: 'current' is a node owned by the list
+                unsafe { ptr.as_ref() }.prev
+            },
+        };
+        // SAFETY: 'prev' is owned by the list
+        unsafe { self.list.link_after(prev, node) }
+    }
+
+    #[inline]
+    fn remove_current_node(&mut self) -> Option<Box<Node<T>>> {
+        let ptr = self.current?;
+        // SAFETY: 'current' is a node owned by the list
+        self.current = unsafe { ptr.as_ref() }.next;
+        // SAFETY: 'current' was a node owned by the list
+        unsafe { self.list.unlink(Some(ptr)) }
+    }
+
+    /// Inserts a boxed value after the current element, or at the front of
+    /// the list when at the ghost position.
+    #[inline]
+    pub fn insert_after_boxed(&mut self, data: Box<T>) {
+        self.insert_node_after(Node::build(data, None, None))
+    }
+
+    /// Inserts a boxed value before the current element, or at the back of
+    /// the list when at the ghost position.
+    #[inline]
+    pub fn insert_before_boxed(&mut self, data: Box<T>) {
+        self.insert_node_before(Node::build(data, None, None))
+    }
+
+    /// Removes the current element and moves to the next one.
+    ///
+    /// Returns `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn remove_current_boxed(&mut self) -> Option<Box<T>> {
+        self.remove_current_node().map(|node| node.split().0)
+    }
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Inserts a value after the current element, or at the front of the
+    /// list when at the ghost position.
+    #[inline]
+    pub fn insert_after(&mut self, data: T) {
+        self.insert_node_after(Box::new(Node { prev: None, next: None, data }))
+    }
+
+    /// Inserts a value before the current element, or at the back of the
+    /// list when at the ghost position.
+    #[inline]
+    pub fn insert_before(&mut self, data: T) {
+        self.insert_node_before(Box::new(Node { prev: None, next: None, data }))
+    }
+
+    /// Removes the current element and moves to the next one.
+    ///
+    /// Returns `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.remove_current_node().map(|node| node.data)
+    }
+}
+
+impl<T: ?Sized> DoublyLinkedList<T> {
+    /// Cursor at the head of the list, or at the ghost position if the
+    /// list is empty.
+    #[must_use]
+    #[inline]
+    pub const fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor { current: self.head, index: 0, list: self }
+    }
+
+    /// Cursor at the tail of the list, or at the ghost position if the
+    /// list is empty.
+    #[must_use]
+    #[inline]
+    pub const fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor { current: self.tail, index: self.length.wrapping_sub(1), list: self }
+    }
+
+    /// Mutable cursor at the head of the list, or at the ghost position if
+    /// the list is empty.
+    #[must_use]
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { current: self.head, index: 0, list: self }
+    }
+
+    /// Mutable cursor at the tail of the list, or at the ghost position if
+    /// the list is empty.
+    #[must_use]
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let index = self.length.wrapping_sub(1);
+        CursorMut { current: self.tail, index, list: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(list: &DoublyLinkedList<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn walk_both_ways() {
+        let mut list = DoublyLinkedList::new();
+        for i in 0..3 {
+            list.push_back(i)
+        }
+
+        let mut cursor = list.cursor_back();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&2)));
+        assert_eq!((cursor.peek_prev(), cursor.peek_next()), (Some(&1), None));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!((cursor.peek_prev(), cursor.peek_next()), (Some(&2), Some(&0)));
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&0)));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&2)))
+    }
+
+    #[test]
+    fn edit_around_cursor() {
+        let mut list = DoublyLinkedList::new();
+        let mut cursor = list.cursor_front_mut();
+
+        // at ghost: after is front, before is back
+        cursor.insert_after(2);
+        cursor.insert_before(4);
+        cursor.move_next();
+        cursor.insert_before(1);
+        cursor.insert_after(3);
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 2)));
+
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 2)));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        assert_eq!(contents(&list), vec![2, 3]);
+        assert_eq!((list.front(), list.back(), list.len()), (Some(&2), Some(&3), 2));
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![3, 2])
+    }
+
+    #[test]
+    fn unsized_cursor() {
+        let mut list = DoublyLinkedList::<[u8]>::new();
+        list.push_back_boxed(Box::new([2, 2]));
+
+        let mut cursor = list.cursor_back_mut();
+        cursor.insert_before_boxed(Box::new([1]));
+        cursor.insert_after_boxed(Box::new([3, 3, 3]));
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current_boxed().as_deref(), Some(&[1][..]));
+        assert_eq!(cursor.current(), Some(&mut [2, 2][..]));
+
+        let lengths: Vec<_> = list.iter().map(<[u8]>::len).collect();
+        assert_eq!(lengths, vec![2, 3]);
+        assert_eq!(list.back(), Some(&[3, 3, 3][..]))
+    }
+}
@@ -0,0 +1,311 @@
+use super::list::DoublyLinkedList;
+use super::node::{Link, Node};
+
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// Borrowing iterator over the elements of a [`DoublyLinkedList`].
+///
+/// Created by [`DoublyLinkedList::iter`].
+pub struct Iter<'a, T: ?Sized> {
+    head: Link<T>,
+    tail: Link<T>,
+    length: usize,
+    marker: PhantomData<&'a Node<T>>
+}
+
+// SAFETY: behaves like a '&T'
+unsafe impl<T: ?Sized + Sync> Send for Iter<'_, T> {}
+// SAFETY: behaves like a '&T'
+unsafe impl<T: ?Sized + Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        if self.length == 0 {
+            return None
+        }
+        self.head.map(|ptr| {
+            // SAFETY: the list is borrowed and 'length' avoids
+            // crossing 'tail'
+            let node = unsafe { &*ptr.as_ptr() };
+            self.head = node.next;
+            self.length -= 1;
+            &node.data
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<'a, T: ?Sized> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.length == 0 {
+            return None
+        }
+        self.tail.map(|ptr| {
+            // SAFETY: the list is borrowed and 'length' avoids
+            // crossing 'head'
+            let node = unsafe { &*ptr.as_ptr() };
+            self.tail = node.prev;
+            self.length -= 1;
+            &node.data
+        })
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { head: self.head, tail: self.tail, length: self.length, marker: PhantomData }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for Iter<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// Mutable borrowing iterator over the elements of a [`DoublyLinkedList`].
+///
+/// Created by [`DoublyLinkedList::iter_mut`].
+pub struct IterMut<'a, T: ?Sized> {
+    head: Link<T>,
+    tail: Link<T>,
+    length: usize,
+    marker: PhantomData<&'a mut Node<T>>
+}
+
+// SAFETY: behaves like a '&mut T'
+unsafe impl<T: ?Sized + Send> Send for IterMut<'_, T> {}
+// SAFETY: behaves like a '&mut T'
+unsafe impl<T: ?Sized + Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.length == 0 {
+            return None
+        }
+        self.head.map(|ptr| {
+            // SAFETY: the list is mutably borrowed and 'length' avoids
+            // crossing 'tail', so each node is yielded only once
+            let node = unsafe { &mut *ptr.as_ptr() };
+            self.head = node.next;
+            self.length -= 1;
+            &mut node.data
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<'a, T: ?Sized> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        if self.length == 0 {
+            return None
+        }
+        self.tail.map(|ptr| {
+            // SAFETY: the list is mutably borrowed and 'length' avoids
+            // crossing 'head', so each node is yielded only once
+            let node = unsafe { &mut *ptr.as_ptr() };
+            self.tail = node.prev;
+            self.length -= 1;
+            &mut node.data
+        })
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for IterMut<'_, T> {}
+
+impl<T: ?Sized + Debug> Debug for IterMut<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("IterMut").field("length", &self.length).finish()
+    }
+}
+
+/// Owning iterator over the elements of a [`DoublyLinkedList`].
+///
+/// Created by [`DoublyLinkedList::into_iter`](IntoIterator::into_iter).
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    list: DoublyLinkedList<T>
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Owning iterator over the boxed elements of a [`DoublyLinkedList`], for
+/// possibly unsized `T`.
+///
+/// Created by [`DoublyLinkedList::into_iter_boxed`].
+#[derive(Debug)]
+pub struct IntoIterBoxed<T: ?Sized> {
+    list: DoublyLinkedList<T>
+}
+
+impl<T: ?Sized> Iterator for IntoIterBoxed<T> {
+    type Item = Box<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Box<T>> {
+        self.list.pop_front_boxed()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T: ?Sized> DoubleEndedIterator for IntoIterBoxed<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Box<T>> {
+        self.list.pop_back_boxed()
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for IntoIterBoxed<T> {}
+
+impl<T: ?Sized> FusedIterator for IntoIterBoxed<T> {}
+
+impl<T: ?Sized> DoublyLinkedList<T> {
+    #[must_use]
+    #[inline]
+    pub const fn iter(&self) -> Iter<'_, T> {
+        Iter { head: self.head, tail: self.tail, length: self.length, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { head: self.head, tail: self.tail, length: self.length, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn into_iter_boxed(self) -> IntoIterBoxed<T> {
+        IntoIterBoxed { list: self }
+    }
+}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for &'a DoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for &'a mut DoublyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_ended() {
+        let mut list = DoublyLinkedList::new();
+        for i in 0..5 {
+            list.push_back(i)
+        }
+
+        let mut iter = list.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(&0), Some(&4)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.rev().collect::<Vec<_>>(), vec![&3, &2, &1]);
+
+        let mut iter = list.iter_mut();
+        if let Some(x) = iter.next_back() {
+            *x = 40
+        }
+        for x in iter {
+            *x *= 10
+        }
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next_back(), Some(40));
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![10, 20, 30])
+    }
+
+    #[test]
+    fn meet_in_the_middle() {
+        let mut list = DoublyLinkedList::<str>::new();
+        list.push_back_boxed("a".into());
+        list.push_back_boxed("b".into());
+
+        let mut iter = list.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some("a"), Some("b")));
+        assert_eq!((iter.next(), iter.next_back()), (None, None));
+
+        let mut iter = list.into_iter_boxed();
+        assert_eq!(iter.next_back().as_deref(), Some("b"));
+        assert_eq!(iter.next_back().as_deref(), Some("a"));
+        assert_eq!(iter.next(), None)
+    }
+}
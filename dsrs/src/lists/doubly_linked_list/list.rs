@@ -0,0 +1,263 @@
+use super::node::{Link, Node};
+
+use std::fmt::{Debug, Formatter, Result};
+use std::marker::PhantomData;
+
+pub struct DoublyLinkedList<T: ?Sized> {
+    pub(super) head: Link<T>,
+    pub(super) tail: Link<T>,
+    pub(super) length: usize,
+    pub(super) marker: PhantomData<Box<Node<T>>>
+}
+
+// SAFETY: the list owns its nodes, just like a 'Box'
+unsafe impl<T: ?Sized + Send> Send for DoublyLinkedList<T> {}
+// SAFETY: shared references only give shared access to the nodes
+unsafe impl<T: ?Sized + Sync> Sync for DoublyLinkedList<T> {}
+
+impl<T: ?Sized> DoublyLinkedList<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { head: None, tail: None, length: 0, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: 'head' is a node owned by the list
+        self.head.map(|ptr| unsafe { &(*ptr.as_ptr()).data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: 'head' is a node owned by the list, borrowed mutably
+        self.head.map(|ptr| unsafe { &mut (*ptr.as_ptr()).data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: 'tail' is a node owned by the list
+        self.tail.map(|ptr| unsafe { &(*ptr.as_ptr()).data })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: 'tail' is a node owned by the list, borrowed mutably
+        self.tail.map(|ptr| unsafe { &mut (*ptr.as_ptr()).data })
+    }
+}
+
+impl<T: ?Sized> DoublyLinkedList<T> {
+    /// Links `node` right after `prev`, or at the head when `prev` is
+    /// `None`.
+    ///
+    /// # Safety
+    ///
+    /// `prev` must be a node owned by this list.
+    #[inline]
+    pub(super) unsafe fn link_after(&mut self, prev: Link<T>, mut node: Box<Node<T>>) {
+        let next = match prev {
+            // SAFETY: 'prev' is in the list
+            Some(ptr) => unsafe { ptr.as_ref() }.next,
+            None => self.head
+        };
+        node.prev = prev;
+        node.next = next;
+        let ptr = node.into_ptr();
+
+        match prev {
+            // SAFETY: 'prev' is in the list
+            Some(prev) => unsafe { (*prev.as_ptr()).next = Some(ptr) },
+            None => self.head = Some(ptr)
+        }
+        match next {
+            // SAFETY: 'next' is in the list
+            Some(next) => unsafe { (*next.as_ptr()).prev = Some(ptr) },
+            None => self.tail = Some(ptr)
+        }
+        self.length += 1
+    }
+
+    /// Removes a node from the list, taking back its ownership.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a node owned by this list.
+    #[inline]
+    pub(super) unsafe fn unlink(&mut self, ptr: Link<T>) -> Option<Box<Node<T>>> {
+        // SAFETY: 'ptr' is in the list, so its ownership is here
+        let mut node = unsafe { Node::from_ptr(ptr?) };
+
+        match node.prev {
+            // SAFETY: 'prev' is in the list
+            Some(prev) => unsafe { (*prev.as_ptr()).next = node.next },
+            None => self.head = node.next
+        }
+        match node.next {
+            // SAFETY: 'next' is in the list
+            Some(next) => unsafe { (*next.as_ptr()).prev = node.prev },
+            None => self.tail = node.prev
+        }
+        node.prev = None;
+        node.next = None;
+        self.length -= 1;
+        Some(node)
+    }
+
+    #[inline]
+    pub fn push_front_boxed(&mut self, data: Box<T>) {
+        // SAFETY: None is always valid
+        unsafe { self.link_after(None, Node::build(data, None, None)) }
+    }
+
+    #[inline]
+    pub fn push_back_boxed(&mut self, data: Box<T>) {
+        // SAFETY: 'tail' is owned by the list
+        unsafe { self.link_after(self.tail, Node::build(data, None, None)) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop_front_boxed(&mut self) -> Option<Box<T>> {
+        // SAFETY: 'head' is owned by the list
+        unsafe { self.unlink(self.head) }.map(|node| node.split().0)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop_back_boxed(&mut self) -> Option<Box<T>> {
+        // SAFETY: 'tail' is owned by the list
+        unsafe { self.unlink(self.tail) }.map(|node| node.split().0)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        let mut next = self.head.take();
+        self.tail = None;
+        self.length = 0;
+
+        while let Some(ptr) = next {
+            // SAFETY: every node is owned by the list and is only
+            // visited once
+            let node = unsafe { Node::from_ptr(ptr) };
+            next = node.next
+        }
+    }
+}
+
+impl<T> DoublyLinkedList<T> {
+    #[inline]
+    pub fn push_front(&mut self, data: T) {
+        let node = Box::new(Node { prev: None, next: None, data });
+        // SAFETY: None is always valid
+        unsafe { self.link_after(None, node) }
+    }
+
+    #[inline]
+    pub fn push_back(&mut self, data: T) {
+        let node = Box::new(Node { prev: None, next: None, data });
+        // SAFETY: 'tail' is owned by the list
+        unsafe { self.link_after(self.tail, node) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        // SAFETY: 'head' is owned by the list
+        unsafe { self.unlink(self.head) }.map(|node| node.data)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        // SAFETY: 'tail' is owned by the list
+        unsafe { self.unlink(self.tail) }.map(|node| node.data)
+    }
+}
+
+impl<T: ?Sized> Drop for DoublyLinkedList<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<T: ?Sized> Default for DoublyLinkedList<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for DoublyLinkedList<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_and_pop_both_ends() {
+        let mut list = DoublyLinkedList::new();
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!((list.front(), list.back(), list.len()), (Some(&1), Some(&3), 3));
+
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!((list.front(), list.back()), (Some(&2), Some(&2)));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!((list.front(), list.back(), list.len()), (None, None, 0));
+        assert_eq!(list.pop_front(), None)
+    }
+
+    #[test]
+    fn unsized_payloads() {
+        let mut list = DoublyLinkedList::<str>::new();
+        list.push_back_boxed("middle".into());
+        list.push_front_boxed("first".into());
+        list.push_back_boxed("last".into());
+        if let Some(last) = list.back_mut() {
+            last.make_ascii_uppercase()
+        }
+
+        assert_eq!(format!("{:?}", list), r#"["first", "middle", "LAST"]"#);
+        assert_eq!(list.pop_back_boxed().as_deref(), Some("LAST"));
+        assert_eq!(list.pop_front_boxed().as_deref(), Some("first"));
+        assert_eq!(list.len(), 1);
+
+        let mut bytes = DoublyLinkedList::<[u8]>::new();
+        bytes.push_back_boxed(vec![1, 2, 3].into_boxed_slice());
+        assert_eq!(bytes.front(), Some(&[1, 2, 3][..]))
+    }
+
+    #[test]
+    fn drop_long_list() {
+        let mut list = DoublyLinkedList::new();
+        for i in 0..1_000_000_u32 {
+            list.push_back(i)
+        }
+        drop(list)
+    }
+}
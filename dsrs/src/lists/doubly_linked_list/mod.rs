@@ -0,0 +1,8 @@
+mod node;
+mod list;
+mod iter;
+mod cursor;
+
+pub use list::DoublyLinkedList;
+pub use iter::{IntoIter, IntoIterBoxed, Iter, IterMut};
+pub use cursor::{Cursor, CursorMut};
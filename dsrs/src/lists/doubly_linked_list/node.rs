@@ -0,0 +1,44 @@
+use mem::alloc::ReprC;
+use mem::ptr::NonNull;
+use mem::ReprC;
+
+pub(super) type Link<T> = Option<NonNull<Node<T>>>;
+
+#[repr(C)]
+#[derive(Debug, ReprC)]
+pub(super) struct Node<T: ?Sized> {
+    pub prev: Link<T>,
+    pub next: Link<T>,
+    pub data: T
+}
+
+impl<T: ?Sized> Node<T> {
+    #[inline]
+    pub fn build(data: Box<T>, prev: Link<T>, next: Link<T>) -> Box<Self> {
+        ReprC::expand(data, (prev, next))
+    }
+
+    #[inline]
+    pub fn split(self: Box<Self>) -> (Box<T>, Link<T>, Link<T>) {
+        let (data, (prev, next)) = ReprC::split(self, true);
+        (data, prev, next)
+    }
+
+    /// Gives up ownership of the node, which must be later recovered
+    /// with [`Node::from_ptr`].
+    #[inline]
+    pub fn into_ptr(self: Box<Self>) -> NonNull<Self> {
+        // SAFETY: a box is never null
+        unsafe { NonNull::new_unchecked(Box::into_raw(self)) }
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must come from [`Node::into_ptr`] and must not be used
+    /// after this.
+    #[inline]
+    pub unsafe fn from_ptr(ptr: NonNull<Self>) -> Box<Self> {
+        // SAFETY: guaranteed by the caller
+        unsafe { Box::from_raw(ptr.as_ptr()) }
+    }
+}
@@ -0,0 +1,306 @@
+use super::link::{container_of, Adapter, Link};
+use super::list::{IntrusiveList, LinkPtr};
+
+/// Read-only bidirectional cursor over an [`IntrusiveList`].
+///
+/// A cursor points either to a value of the list or to a "ghost"
+/// position, between the tail and the head.
+///
+/// Created by [`IntrusiveList::cursor_front`] or
+/// [`IntrusiveList::cursor_back`].
+pub struct Cursor<'c, 'a, A: Adapter> {
+    current: LinkPtr,
+    index: usize,
+    list: &'c IntrusiveList<'a, A>
+}
+
+impl<A: Adapter> Clone for Cursor<'_, '_, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { current: self.current, index: self.index, list: self.list }
+    }
+}
+
+impl<'a, A: Adapter> Cursor<'_, 'a, A> {
+    /// Position of the current value, or `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn current(&self) -> Option<&'a A::Value> {
+        // SAFETY: 'current' is in the borrowed list
+        self.current.map(|ptr| unsafe { container_of::<A>(ptr) })
+    }
+
+    /// Value after the current one. At the ghost position, this is the
+    /// head of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_next(&self) -> Option<&'a A::Value> {
+        // SAFETY: 'current' is in the borrowed list
+        let next = unsafe { next_of(self.current, self.list) };
+        // SAFETY: 'next' is in the borrowed list
+        next.map(|ptr| unsafe { container_of::<A>(ptr) })
+    }
+
+    /// Value before the current one. At the ghost position, this is the
+    /// tail of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_prev(&self) -> Option<&'a A::Value> {
+        // SAFETY: 'current' is in the borrowed list
+        let prev = unsafe { prev_of(self.current, self.list) };
+        // SAFETY: 'prev' is in the borrowed list
+        prev.map(|ptr| unsafe { container_of::<A>(ptr) })
+    }
+
+    /// Moves to the next value, going through the ghost position after
+    /// the tail.
+    #[inline]
+    pub fn move_next(&mut self) {
+        // SAFETY: 'current' is in the borrowed list
+        self.current = unsafe { next_of(self.current, self.list) };
+        self.index = if self.current == self.list.head { 0 } else { self.index + 1 }
+    }
+
+    /// Moves to the previous value, going through the ghost position
+    /// before the head.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        let at_ghost = self.current.is_none();
+        // SAFETY: 'current' is in the borrowed list
+        self.current = unsafe { prev_of(self.current, self.list) };
+        self.index = if at_ghost { self.list.len() } else { self.index }.wrapping_sub(1)
+    }
+}
+
+/// Bidirectional cursor over an [`IntrusiveList`] with editing
+/// operations.
+///
+/// Just like [`Cursor`], this may point to a "ghost" position, between the
+/// tail and the head.
+///
+/// Created by [`IntrusiveList::cursor_front_mut`] or
+/// [`IntrusiveList::cursor_back_mut`].
+pub struct CursorMut<'c, 'a, A: Adapter> {
+    current: LinkPtr,
+    index: usize,
+    list: &'c mut IntrusiveList<'a, A>
+}
+
+impl<'a, A: Adapter> CursorMut<'_, 'a, A> {
+    /// Read-only cursor at the same position.
+    #[must_use]
+    #[inline]
+    pub fn as_cursor(&self) -> Cursor<'_, 'a, A> {
+        Cursor { current: self.current, index: self.index, list: self.list }
+    }
+
+    /// Position of the current value, or `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.as_cursor().index()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn current(&self) -> Option<&'a A::Value> {
+        self.as_cursor().current()
+    }
+
+    /// Value after the current one. At the ghost position, this is the
+    /// head of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_next(&self) -> Option<&'a A::Value> {
+        self.as_cursor().peek_next()
+    }
+
+    /// Value before the current one. At the ghost position, this is the
+    /// tail of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_prev(&self) -> Option<&'a A::Value> {
+        self.as_cursor().peek_prev()
+    }
+
+    /// Moves to the next value, going through the ghost position after
+    /// the tail.
+    #[inline]
+    pub fn move_next(&mut self) {
+        let mut cursor = self.as_cursor();
+        cursor.move_next();
+        let (current, index) = (cursor.current, cursor.index);
+        self.current = current;
+        self.index = index
+    }
+
+    /// Moves to the previous value, going through the ghost position
+    /// before the head.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        let mut cursor = self.as_cursor();
+        cursor.move_prev();
+        let (current, index) = (cursor.current, cursor.index);
+        self.current = current;
+        self.index = index
+    }
+
+    /// Links a value after the current one, or at the front of the list
+    /// when at the ghost position.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is already linked by this adapter, in any list.
+    #[inline]
+    pub fn insert_after(&mut self, value: &'a A::Value) {
+        // SAFETY: 'current' is in the list
+        unsafe { self.list.link_after(self.current, value) }
+    }
+
+    /// Links a value before the current one, or at the back of the list
+    /// when at the ghost position.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is already linked by this adapter, in any list.
+    #[inline]
+    pub fn insert_before(&mut self, value: &'a A::Value) {
+        // SAFETY: 'current' is in the list
+        let prev = unsafe { prev_of(self.current, self.list) };
+        // SAFETY: 'prev' is in the list
+        unsafe { self.list.link_after(prev, value) };
+        if self.current.is_some() {
+            self.index += 1
+        }
+    }
+
+    /// Unlinks the current value and moves to the next one.
+    ///
+    /// Returns `None` at the ghost position.
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<&'a A::Value> {
+        let ptr = self.current?;
+        // SAFETY: 'current' is in the list
+        unsafe {
+            self.current = Link::get(ptr).next.get();
+            Some(self.list.unlink(ptr))
+        }
+    }
+}
+
+/// # Safety
+///
+/// `current` must be in `list`.
+#[inline]
+unsafe fn next_of<A: Adapter>(current: LinkPtr, list: &IntrusiveList<'_, A>) -> LinkPtr {
+    match current {
+        None => list.head,
+        // SAFETY: guaranteed by the caller
+        Some(ptr) => unsafe { Link::get(ptr) }.next.get()
+    }
+}
+
+/// # Safety
+///
+/// `current` must be in `list`.
+#[inline]
+unsafe fn prev_of<A: Adapter>(current: LinkPtr, list: &IntrusiveList<'_, A>) -> LinkPtr {
+    match current {
+        None => list.tail,
+        // SAFETY: guaranteed by the caller
+        Some(ptr) => unsafe { Link::get(ptr) }.prev.get()
+    }
+}
+
+impl<'a, A: Adapter> IntrusiveList<'a, A> {
+    /// Cursor at the head of the list, or at the ghost position if the
+    /// list is empty.
+    #[must_use]
+    #[inline]
+    pub const fn cursor_front(&self) -> Cursor<'_, 'a, A> {
+        Cursor { current: self.head, index: 0, list: self }
+    }
+
+    /// Cursor at the tail of the list, or at the ghost position if the
+    /// list is empty.
+    #[must_use]
+    #[inline]
+    pub const fn cursor_back(&self) -> Cursor<'_, 'a, A> {
+        Cursor { current: self.tail, index: self.length.wrapping_sub(1), list: self }
+    }
+
+    /// Mutable cursor at the head of the list, or at the ghost position if
+    /// the list is empty.
+    #[must_use]
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, 'a, A> {
+        CursorMut { current: self.head, index: 0, list: self }
+    }
+
+    /// Mutable cursor at the tail of the list, or at the ghost position if
+    /// the list is empty.
+    #[must_use]
+    #[inline]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, 'a, A> {
+        let index = self.length.wrapping_sub(1);
+        CursorMut { current: self.tail, index, list: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::list::tests::{arena, ids, AllTasks, ReadyTasks};
+    use super::*;
+
+    #[test]
+    fn walk_both_ways() {
+        let tasks = arena(3);
+        let mut list = IntrusiveList::<ReadyTasks>::new();
+        list.extend(&tasks);
+
+        let mut cursor = list.cursor_back();
+        assert_eq!((cursor.index(), cursor.current().map(|task| task.id)), (Some(2), Some(2)));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current().map(|task| task.id)), (None, None));
+        assert_eq!(cursor.peek_next().map(|task| task.id), Some(0));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.peek_prev().map(|task| task.id)), (Some(1), Some(0)));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current().map(|task| task.id)), (Some(0), Some(0)))
+    }
+
+    #[test]
+    fn edit_in_place() {
+        let tasks = arena(5);
+        let mut list = IntrusiveList::<AllTasks>::new();
+        list.push_back(&tasks[0]);
+        list.push_back(&tasks[2]);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(&tasks[1]);
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_before(&tasks[3]);
+        assert_eq!(cursor.index(), Some(3));
+        cursor.move_next();
+        cursor.insert_before(&tasks[4]);
+        assert_eq!(ids(&list), vec![0, 1, 3, 2, 4]);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current().map(|task| task.id), Some(1));
+        assert_eq!(cursor.current().map(|task| task.id), Some(3));
+        assert!(!tasks[1].all.is_linked());
+        assert_eq!(ids(&list), vec![0, 3, 2, 4]);
+        assert_eq!(list.len(), 4)
+    }
+}
@@ -0,0 +1,106 @@
+use mem::alloc::Layout;
+use mem::ptr::NonNull;
+
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter, Result};
+
+/// Field that places its container in an [`IntrusiveList`].
+///
+/// A struct can be in as many lists at once as it has links, each one with
+/// its own [`Adapter`]. The link is changed through shared references, so
+/// the values can stay borrowed from an arena.
+///
+/// [`IntrusiveList`]: super::IntrusiveList
+pub struct Link {
+    pub(super) prev: Cell<Option<NonNull<Self>>>,
+    pub(super) next: Cell<Option<NonNull<Self>>>,
+    // id of the list holding the link, zero when unlinked
+    pub(super) owner: Cell<usize>
+}
+
+impl Link {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { prev: Cell::new(None), next: Cell::new(None), owner: Cell::new(0) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_linked(&self) -> bool {
+        self.owner.get() != 0
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must point to a live link during `'a`.
+    #[inline]
+    pub(super) unsafe fn get<'a>(ptr: NonNull<Self>) -> &'a Self {
+        // SAFETY: guaranteed by the caller
+        unsafe { &*ptr.as_ptr() }
+    }
+}
+
+impl Default for Link {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Link {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("Link").field("is_linked", &self.is_linked()).finish()
+    }
+}
+
+/// Maps between a value and one of its [`Link`] fields.
+///
+/// # Safety
+///
+/// `OFFSET` must be the offset in bytes of a `Link` field inside `Value`.
+/// For `#[repr(C)]` structs, this can be computed with [`field_offset`].
+pub unsafe trait Adapter {
+    type Value;
+
+    const OFFSET: usize;
+
+    #[must_use]
+    #[inline]
+    fn link(value: &Self::Value) -> &Link {
+        // SAFETY: the link is a field of the value, so it lives as long
+        unsafe { Link::get(link_of::<Self>(NonNull::from(value))) }
+    }
+}
+
+/// Offset of the last of `fields`, in a `#[repr(C)]` struct that starts
+/// with them.
+///
+/// # Panics
+///
+/// If the fields overflow the maximum size of a struct.
+#[must_use]
+#[inline]
+pub const fn field_offset<const N: usize>(fields: [Layout; N]) -> usize {
+    match Layout::EMPTY.extend_many(fields) {
+        Ok((_, offsets)) => offsets[N - 1],
+        Err(_) => panic!("fields are too big for a struct")
+    }
+}
+
+/// Pointer to the link of `value`, keeping its access to the whole value.
+#[inline]
+pub(super) fn link_of<A: Adapter + ?Sized>(value: NonNull<A::Value>) -> NonNull<Link> {
+    // SAFETY: guaranteed by the adapter
+    unsafe { NonNull::new_unchecked(value.as_ptr().cast::<u8>().add(A::OFFSET).cast()) }
+}
+
+/// # Safety
+///
+/// `link` must have been built by [`link_of`] with the same adapter.
+#[inline]
+pub(super) unsafe fn container_of<'a, A: Adapter + ?Sized>(link: NonNull<Link>) -> &'a A::Value {
+    // SAFETY: guaranteed by the caller
+    unsafe { &*link.as_ptr().cast::<u8>().sub(A::OFFSET).cast() }
+}
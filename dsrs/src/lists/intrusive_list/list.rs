@@ -0,0 +1,396 @@
+use super::link::{container_of, link_of, Adapter, Link};
+
+use mem::ptr::NonNull;
+
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub(super) type LinkPtr = Option<NonNull<Link>>;
+
+/// Source of unique list ids, zero is reserved for unlinked values.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Doubly linked list of values that embed their own [`Link`].
+///
+/// The list never allocates nor owns its values, it just borrows them for
+/// `'a`, so they can live anywhere, like in an arena. Since every link
+/// knows the list holding it, values can be removed by reference in
+/// *O*(1).
+pub struct IntrusiveList<'a, A: Adapter> {
+    pub(super) head: LinkPtr,
+    pub(super) tail: LinkPtr,
+    pub(super) length: usize,
+    id: usize,
+    marker: PhantomData<&'a A::Value>
+}
+
+impl<'a, A: Adapter> IntrusiveList<'a, A> {
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        Self { head: None, tail: None, length: 0, id, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn front(&self) -> Option<&'a A::Value> {
+        // SAFETY: 'head' is in the list
+        self.head.map(|link| unsafe { container_of::<A>(link) })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn back(&self) -> Option<&'a A::Value> {
+        // SAFETY: 'tail' is in the list
+        self.tail.map(|link| unsafe { container_of::<A>(link) })
+    }
+
+    /// Whether `value` is linked in this list, in *O*(1).
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, value: &A::Value) -> bool {
+        A::link(value).owner.get() == self.id
+    }
+
+    /// Links `value` right after `prev`, or at the head when `prev` is
+    /// `None`.
+    ///
+    /// # Safety
+    ///
+    /// `prev` must be a link in this list.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is already linked by this adapter.
+    #[inline]
+    pub(super) unsafe fn link_after(&mut self, prev: LinkPtr, value: &'a A::Value) {
+        let ptr = link_of::<A>(NonNull::from(value));
+        // SAFETY: 'ptr' was built from a reference
+        let link = unsafe { Link::get(ptr) };
+        assert!(!link.is_linked(), "value is already in a list");
+
+        let next = match prev {
+            // SAFETY: 'prev' is in the list
+            Some(prev) => unsafe { Link::get(prev) }.next.get(),
+            None => self.head
+        };
+        link.prev.set(prev);
+        link.next.set(next);
+        link.owner.set(self.id);
+
+        match prev {
+            // SAFETY: 'prev' is in the list
+            Some(prev) => unsafe { Link::get(prev) }.next.set(Some(ptr)),
+            None => self.head = Some(ptr)
+        }
+        match next {
+            // SAFETY: 'next' is in the list
+            Some(next) => unsafe { Link::get(next) }.prev.set(Some(ptr)),
+            None => self.tail = Some(ptr)
+        }
+        self.length += 1
+    }
+
+    /// Removes a link from the list, returning its value.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a link in this list.
+    #[inline]
+    pub(super) unsafe fn unlink(&mut self, ptr: NonNull<Link>) -> &'a A::Value {
+        // SAFETY: 'ptr' is in the list
+        let link = unsafe { Link::get(ptr) };
+        let (prev, next) = (link.prev.get(), link.next.get());
+
+        match prev {
+            // SAFETY: 'prev' is in the list
+            Some(prev) => unsafe { Link::get(prev) }.next.set(next),
+            None => self.head = next
+        }
+        match next {
+            // SAFETY: 'next' is in the list
+            Some(next) => unsafe { Link::get(next) }.prev.set(prev),
+            None => self.tail = prev
+        }
+        link.prev.set(None);
+        link.next.set(None);
+        link.owner.set(0);
+        self.length -= 1;
+        // SAFETY: every link in the list was built by 'link_of'
+        unsafe { container_of::<A>(ptr) }
+    }
+
+    /// # Panics
+    ///
+    /// If `value` is already linked by this adapter, in any list.
+    #[inline]
+    pub fn push_front(&mut self, value: &'a A::Value) {
+        // SAFETY: None is always valid
+        unsafe { self.link_after(None, value) }
+    }
+
+    /// # Panics
+    ///
+    /// If `value` is already linked by this adapter, in any list.
+    #[inline]
+    pub fn push_back(&mut self, value: &'a A::Value) {
+        // SAFETY: 'tail' is in the list
+        unsafe { self.link_after(self.tail, value) }
+    }
+
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<&'a A::Value> {
+        // SAFETY: 'head' is in the list
+        self.head.map(|link| unsafe { self.unlink(link) })
+    }
+
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<&'a A::Value> {
+        // SAFETY: 'tail' is in the list
+        self.tail.map(|link| unsafe { self.unlink(link) })
+    }
+
+    /// Unlinks `value`, in *O*(1).
+    ///
+    /// Returns `false` if it wasn't in this list.
+    #[inline]
+    pub fn remove(&mut self, value: &A::Value) -> bool {
+        if !self.contains(value) {
+            return false
+        }
+        // SAFETY: the value is in this list
+        unsafe { self.unlink(link_of::<A>(NonNull::from(value))) };
+        true
+    }
+
+    /// Unlinks every value, so they can be added to other lists.
+    #[inline]
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, A> {
+        Iter { head: self.head, tail: self.tail, length: self.length, marker: PhantomData }
+    }
+}
+
+impl<A: Adapter> Drop for IntrusiveList<'_, A> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<A: Adapter> Default for IntrusiveList<'_, A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Adapter> Debug for IntrusiveList<'_, A>
+where A::Value: Debug {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, A: Adapter> Extend<&'a A::Value> for IntrusiveList<'a, A> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a A::Value>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value)
+        }
+    }
+}
+
+/// Borrowing iterator over the values of an [`IntrusiveList`].
+///
+/// Created by [`IntrusiveList::iter`].
+pub struct Iter<'a, A: Adapter> {
+    head: LinkPtr,
+    tail: LinkPtr,
+    length: usize,
+    marker: PhantomData<&'a A::Value>
+}
+
+impl<'a, A: Adapter> Iterator for Iter<'a, A> {
+    type Item = &'a A::Value;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a A::Value> {
+        if self.length == 0 {
+            return None
+        }
+        self.head.map(|ptr| {
+            self.length -= 1;
+            // SAFETY: the list is borrowed for 'a, and its values
+            // live even longer
+            unsafe {
+                self.head = Link::get(ptr).next.get();
+                container_of::<A>(ptr)
+            }
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<'a, A: Adapter> DoubleEndedIterator for Iter<'a, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a A::Value> {
+        if self.length == 0 {
+            return None
+        }
+        self.tail.map(|ptr| {
+            self.length -= 1;
+            // SAFETY: the list is borrowed for 'a, and its values
+            // live even longer
+            unsafe {
+                self.tail = Link::get(ptr).prev.get();
+                container_of::<A>(ptr)
+            }
+        })
+    }
+}
+
+impl<A: Adapter> ExactSizeIterator for Iter<'_, A> {}
+
+impl<A: Adapter> FusedIterator for Iter<'_, A> {}
+
+impl<A: Adapter> Clone for Iter<'_, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { head: self.head, tail: self.tail, length: self.length, marker: PhantomData }
+    }
+}
+
+impl<'l, A: Adapter> IntoIterator for &'l IntrusiveList<'_, A> {
+    type Item = &'l A::Value;
+    type IntoIter = Iter<'l, A>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'l, A> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::super::field_offset;
+    use super::*;
+    use mem::alloc::Layout;
+
+    #[repr(C)]
+    #[derive(Debug, Default)]
+    pub struct Task {
+        pub id: u32,
+        pub all: Link,
+        pub ready: Link
+    }
+
+    pub struct AllTasks;
+
+    // SAFETY: 'all' is the second field of a repr(C) struct
+    unsafe impl Adapter for AllTasks {
+        type Value = Task;
+
+        const OFFSET: usize = field_offset([Layout::new::<u32>(), Layout::new::<Link>()]);
+    }
+
+    pub struct ReadyTasks;
+
+    // SAFETY: 'ready' is the third field of a repr(C) struct
+    unsafe impl Adapter for ReadyTasks {
+        type Value = Task;
+
+        const OFFSET: usize =
+            field_offset([Layout::new::<u32>(), Layout::new::<Link>(), Layout::new::<Link>()]);
+    }
+
+    pub fn arena(size: u32) -> Vec<Task> {
+        (0..size).map(|id| Task { id, ..Task::default() }).collect()
+    }
+
+    pub fn ids<A: Adapter<Value = Task>>(list: &IntrusiveList<'_, A>) -> Vec<u32> {
+        list.iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn values_in_two_lists() {
+        let tasks = arena(6);
+        let mut all = IntrusiveList::<AllTasks>::new();
+        let mut ready = IntrusiveList::<ReadyTasks>::new();
+
+        all.extend(&tasks);
+        for task in tasks.iter().filter(|task| task.id % 2 == 0) {
+            ready.push_front(task)
+        }
+        assert_eq!(ids(&all), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(ids(&ready), vec![4, 2, 0]);
+        assert!(tasks[3].all.is_linked() && !tasks[3].ready.is_linked());
+
+        assert!(ready.remove(&tasks[2]));
+        assert!(!ready.remove(&tasks[2]) && !ready.remove(&tasks[3]));
+        assert!(all.remove(&tasks[2]));
+        assert_eq!(ids(&all), vec![0, 1, 3, 4, 5]);
+        assert_eq!(ids(&ready), vec![4, 0]);
+
+        assert_eq!(all.pop_back().map(|task| task.id), Some(5));
+        assert_eq!(ready.pop_front().map(|task| task.id), Some(4));
+        assert_eq!((all.len(), ready.len()), (4, 1));
+        assert_eq!(all.iter().rev().map(|task| task.id).collect::<Vec<_>>(), vec![4, 3, 1, 0]);
+        let ends = (all.front().map(|task| task.id), all.back().map(|task| task.id));
+        assert_eq!(ends, (Some(0), Some(4)));
+
+        drop(all);
+        assert!(tasks.iter().all(|task| !task.all.is_linked()));
+        assert!(tasks[0].ready.is_linked())
+    }
+
+    #[test]
+    fn remove_checks_the_list() {
+        let tasks = arena(2);
+        let mut first = IntrusiveList::<AllTasks>::new();
+        let mut second = IntrusiveList::<AllTasks>::new();
+        first.push_back(&tasks[0]);
+        second.push_back(&tasks[1]);
+
+        assert!(!first.remove(&tasks[1]) && !first.contains(&tasks[1]));
+        assert!(second.contains(&tasks[1]));
+        second.clear();
+        first.push_back(&tasks[1]);
+        assert_eq!(ids(&first), vec![0, 1])
+    }
+
+    #[test]
+    #[should_panic(expected = "value is already in a list")]
+    fn push_linked_value() {
+        let tasks = arena(1);
+        let mut first = IntrusiveList::<AllTasks>::new();
+        let mut second = IntrusiveList::<AllTasks>::new();
+        first.push_back(&tasks[0]);
+        second.push_back(&tasks[0])
+    }
+}
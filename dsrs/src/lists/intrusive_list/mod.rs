@@ -0,0 +1,7 @@
+mod link;
+mod list;
+mod cursor;
+
+pub use link::{field_offset, Adapter, Link};
+pub use list::{IntrusiveList, Iter};
+pub use cursor::{Cursor, CursorMut};
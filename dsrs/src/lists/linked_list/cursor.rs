@@ -0,0 +1,437 @@
+use super::list::{LinkedList, SharedAlloc};
+use super::node::{Link, Node};
+
+use mem::ptr::NonNull;
+
+use std::alloc::{AllocRef, Global};
+use std::marker::PhantomData;
+
+/// Read-only cursor over a [`LinkedList`].
+///
+/// A cursor points either to an element of the list or to a "ghost"
+/// position, between the tail and the head.
+///
+/// Created by [`LinkedList::cursor_front`].
+#[derive(Debug)]
+pub struct Cursor<'a, T: ?Sized, A: AllocRef = Global> {
+    current: Option<&'a Node<T>>,
+    index: usize,
+    list: &'a LinkedList<T, A>
+}
+
+impl<T: ?Sized, A: AllocRef> Clone for Cursor<'_, T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { current: self.current, index: self.index, list: self.list }
+    }
+}
+
+impl<'a, T: ?Sized, A: AllocRef> Cursor<'a, T, A> {
+    /// Position of the current element, or `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn current(&self) -> Option<&'a T> {
+        self.current.map(|node| &node.data)
+    }
+
+    #[inline]
+    fn head(&self) -> Option<&'a Node<T>> {
+        // SAFETY: 'head' is a node owned by the list
+        self.list.head.map(|ptr| unsafe { &*ptr.as_ptr() })
+    }
+
+    /// Element after the current one. At the ghost position, this is the
+    /// head of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let next = match self.current {
+            None => self.head(),
+            Some(node) => node.next_ref()
+        };
+        next.map(|node| &node.data)
+    }
+
+    /// Moves to the next element. Moving from the tail leads to the ghost
+    /// position and moving from the ghost leads back to the head.
+    #[inline]
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            None => {
+                self.current = self.head();
+                self.index = 0
+            },
+            Some(node) => {
+                self.current = node.next_ref();
+                self.index += 1
+            },
+        }
+    }
+}
+
+/// Cursor over a [`LinkedList`] with editing operations.
+///
+/// Just like [`Cursor`], this may point to a "ghost" position, between the
+/// tail and the head. Insertions after the ghost go to the front of the
+/// list.
+///
+/// Created by [`LinkedList::cursor_front_mut`].
+#[derive(Debug)]
+pub struct CursorMut<'a, T: ?Sized, A: AllocRef = Global> {
+    current: Link<T>,
+    // node before 'current', 'None' when 'current' is the head
+    prev: Link<T>,
+    index: usize,
+    list: &'a mut LinkedList<T, A>
+}
+
+impl<T: ?Sized, A: AllocRef> CursorMut<'_, T, A> {
+    /// Position of the current element, or `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: 'current' is a node owned by the list, which is
+        // mutably borrowed by the cursor
+        self.current.map(|ptr| unsafe { &mut (*ptr.as_ptr()).data })
+    }
+
+    #[inline]
+    fn next_link(&self) -> Link<T> {
+        match self.current {
+            None => self.list.head,
+            // SAFETY: 'current' is a node owned by the list
+            Some(ptr) => unsafe { ptr.as_ref() }.next
+        }
+    }
+
+    /// Element after the current one. At the ghost position, this is the
+    /// head of the list.
+    #[must_use]
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // SAFETY: the next node is owned by the list, which is
+        // mutably borrowed by the cursor
+        self.next_link().map(|ptr| unsafe { &mut (*ptr.as_ptr()).data })
+    }
+
+    /// Moves to the next element. Moving from the tail leads to the ghost
+    /// position and moving from the ghost leads back to the head.
+    #[inline]
+    pub fn move_next(&mut self) {
+        let next = self.next_link();
+        match self.current.take() {
+            None => {
+                self.prev = None;
+                self.index = 0
+            },
+            Some(ptr) => {
+                self.prev = Some(ptr);
+                self.index += 1
+            },
+        }
+        self.current = next
+    }
+
+    /// Read-only cursor at the same position.
+    #[must_use]
+    #[inline]
+    pub fn as_cursor(&self) -> Cursor<'_, T, A> {
+        Cursor {
+            // SAFETY: 'current' is a node owned by the list
+            current: self.current.map(|ptr| unsafe { &*ptr.as_ptr() }),
+            index: self.index,
+            list: self.list
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `node` must be allocated with the list allocator and
+    /// `node.next` must be `None`.
+    #[inline]
+    pub(super) unsafe fn insert_node_after(&mut self, node: NonNull<Node<T>>) {
+        let ptr = match self.current {
+            // SAFETY: guaranteed by the caller
+            None => return unsafe { self.list.push_node_head(node) },
+            Some(ptr) => ptr
+        };
+        // SAFETY: 'current' is a node owned by the list
+        let current = unsafe { &mut *ptr.as_ptr() };
+        // SAFETY: caller ensures that 'node' is valid
+        let new = unsafe { &mut *node.as_ptr() };
+        debug_assert!(new.next.is_none());
+
+        new.next = current.next.replace(node);
+        if new.next.is_none() {
+            self.list.tail = Some(node)
+        }
+        self.list.length += 1
+    }
+
+    /// Inserts a boxed value after the current element, or at the front of
+    /// the list when at the ghost position.
+    #[inline]
+    pub fn insert_after_boxed(&mut self, data: Box<T>) {
+        let node = Node::build_in(data, None, &mut self.list.alloc);
+        // SAFETY: allocated with the list allocator and next is None
+        unsafe { self.insert_node_after(node) }
+    }
+
+    /// Unlinks the current node, whose ownership goes to the caller.
+    #[inline]
+    pub(super) fn remove_current_node(&mut self) -> Link<T> {
+        let ptr = self.current?;
+
+        match self.prev {
+            None => {
+                let node = self.list.pop_node();
+                debug_assert_eq!(node, Some(ptr));
+            },
+            Some(prev) => {
+                // SAFETY: 'prev' and 'current' are nodes owned by the list
+                let (prev, node) = unsafe { (&mut *prev.as_ptr(), &mut *ptr.as_ptr()) };
+
+                prev.next = node.next.take();
+                if prev.next.is_none() {
+                    self.list.tail = self.prev
+                }
+                self.list.length -= 1
+            },
+        }
+        self.current = match self.prev {
+            None => self.list.head,
+            // SAFETY: 'prev' is still in the list
+            Some(prev) => unsafe { prev.as_ref() }.next
+        };
+        Some(ptr)
+    }
+
+    /// Removes the current element and moves to the next one.
+    ///
+    /// Returns `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn remove_current_boxed(&mut self) -> Option<Box<T>> {
+        let node = self.remove_current_node()?;
+        // SAFETY: the node was unlinked and belongs to the list allocator
+        Some(unsafe { Node::split_in(node, &mut self.list.alloc) }.0)
+    }
+}
+
+impl<T: ?Sized, A: AllocRef + Clone> CursorMut<'_, T, A> {
+    /// Moves every element after the current one into a new list. At the
+    /// ghost position, the whole list is moved.
+    #[must_use]
+    #[inline]
+    pub fn split_after(&mut self) -> LinkedList<T, A> {
+        let ptr = match self.current {
+            None => return self.list.split_off(0),
+            Some(ptr) => ptr
+        };
+        // SAFETY: 'current' is a node owned by the list
+        let head = unsafe { &mut *ptr.as_ptr() }.next.take();
+        let tail = match head {
+            None => None,
+            Some(_) => self.list.tail.replace(ptr)
+        };
+        let length = self.list.length - self.index - 1;
+        self.list.length = self.index + 1;
+
+        LinkedList { head, tail, length, alloc: self.list.alloc.clone(), marker: PhantomData }
+    }
+}
+
+impl<T: ?Sized, A: SharedAlloc> CursorMut<'_, T, A> {
+    /// Inserts all elements of `other` after the current element, or at the
+    /// front of the list when at the ghost position.
+    ///
+    /// This is *O*(1), as no traversal is needed.
+    #[inline]
+    pub fn splice_after(&mut self, mut other: LinkedList<T, A>) {
+        let (head, tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            (_, _) => return
+        };
+
+        let next = match self.current {
+            None => &mut self.list.head,
+            // SAFETY: 'current' is a node owned by the list
+            Some(ptr) => &mut unsafe { &mut *ptr.as_ptr() }.next
+        };
+        match next.replace(head) {
+            // SAFETY: 'other.tail' is the last node in 'head', and its
+            // nodes can be released by this list allocator
+            Some(old) => unsafe { (*tail.as_ptr()).next = Some(old) },
+            None => self.list.tail = Some(tail)
+        }
+
+        self.list.length += other.length;
+        other.length = 0
+    }
+}
+
+impl<T, A: AllocRef> CursorMut<'_, T, A> {
+    /// Inserts a value after the current element, or at the front of the
+    /// list when at the ghost position.
+    #[inline]
+    pub fn insert_after(&mut self, data: T) {
+        let node = Node::new_in(data, None, &mut self.list.alloc);
+        // SAFETY: allocated with the list allocator and next is None
+        unsafe { self.insert_node_after(node) }
+    }
+
+    /// Removes the current element and moves to the next one.
+    ///
+    /// Returns `None` at the ghost position.
+    #[must_use]
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.remove_current_node()?;
+        // SAFETY: the node was unlinked and belongs to the list allocator
+        Some(unsafe { Node::take_in(node, &mut self.list.alloc) }.0)
+    }
+}
+
+impl<T: ?Sized, A: AllocRef> LinkedList<T, A> {
+    /// Cursor at the head of the list, or at the ghost position if the
+    /// list is empty.
+    #[must_use]
+    #[inline]
+    pub fn cursor_front(&self) -> Cursor<'_, T, A> {
+        // SAFETY: 'head' is a node owned by the list
+        let current = self.head.map(|ptr| unsafe { &*ptr.as_ptr() });
+        Cursor { current, index: 0, list: self }
+    }
+
+    /// Mutable cursor at the head of the list, or at the ghost position if
+    /// the list is empty.
+    #[must_use]
+    #[inline]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut { current: self.head, prev: None, index: 0, list: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_of(items: &[i32]) -> LinkedList<i32> {
+        let mut list = LinkedList::new();
+        for &item in items {
+            list.push_tail(item)
+        }
+        list
+    }
+
+    fn contents(list: &LinkedList<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn walk_and_peek() {
+        let list = list_of(&[1, 2, 3]);
+        let mut cursor = list.cursor_front();
+
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&1)));
+        assert_eq!(cursor.peek_next(), Some(&2));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.peek_next(), Some(&1));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&1))
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut list = list_of(&[1, 3]);
+        let mut cursor = list.cursor_front_mut();
+
+        cursor.insert_after(2);
+        cursor.move_next();
+        cursor.move_next();
+        // at the tail, so this must update it
+        cursor.insert_after(4);
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        // at ghost, inserts at front
+        cursor.insert_after(0);
+
+        assert_eq!(contents(&list), vec![0, 1, 2]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.tail(), Some(&2));
+
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(0));
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert!(list.is_empty());
+        assert_eq!(list.tail(), None)
+    }
+
+    #[test]
+    fn split_and_splice() {
+        let mut list = list_of(&[1, 2, 5, 6]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+
+        let rest = cursor.split_after();
+        assert_eq!(contents(&rest), vec![5, 6]);
+        assert_eq!((rest.len(), rest.tail()), (2, Some(&6)));
+
+        cursor.splice_after(list_of(&[3, 4]));
+        cursor.splice_after(LinkedList::new());
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 4));
+        cursor.splice_after(rest);
+        assert_eq!(contents(&list), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!((list.len(), list.tail()), (6, Some(&6)));
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.split_after().len(), 0);
+        cursor.move_next();
+        let all = cursor.split_after();
+        assert_eq!((all.len(), all.tail()), (6, Some(&6)));
+        assert!(list.is_empty())
+    }
+
+    #[test]
+    fn unsized_values() {
+        let mut list = LinkedList::<[u8]>::new();
+        let mut cursor = list.cursor_front_mut();
+
+        cursor.insert_after_boxed(Box::new([1, 2, 3]));
+        cursor.insert_after_boxed(Box::new([]));
+        cursor.move_next();
+        cursor.insert_after_boxed(Box::new([4]));
+        assert_eq!(cursor.remove_current_boxed().as_deref(), Some(&[][..]));
+
+        let items: Vec<_> = list.iter().collect();
+        assert_eq!(items, vec![&[4][..], &[1, 2, 3][..]]);
+        assert_eq!(list.tail(), Some(&[1, 2, 3][..]))
+    }
+}
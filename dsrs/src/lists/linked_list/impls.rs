@@ -1,9 +1,160 @@
+use super::list::LinkedList;
 use super::node::Node;
 
+use std::alloc::AllocRef;
+use std::cmp::Ordering;
 use std::fmt::{Result, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
 
 impl<T: ?Sized> Display for Node<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(f, "node")
     }
 }
+
+impl<T: ?Sized, A: AllocRef + Default> Default for LinkedList<T, A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
+
+impl<T: Clone, A: AllocRef + Clone> Clone for LinkedList<T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut list = Self::new_in(self.alloc.clone());
+        list.extend(self.iter().cloned());
+        list
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, A: AllocRef> Extend<T> for LinkedList<T, A> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_tail(item)
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy, A: AllocRef> Extend<&'a T> for LinkedList<T, A> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+impl<U, T, A, B> PartialEq<LinkedList<U, B>> for LinkedList<T, A>
+where
+    U: ?Sized,
+    T: ?Sized + PartialEq<U>,
+    A: AllocRef,
+    B: AllocRef,
+{
+    #[inline]
+    fn eq(&self, other: &LinkedList<U, B>) -> bool {
+        if self.len() != other.len() {
+            return false
+        }
+
+        match (self.head, other.head) {
+            // SAFETY: both heads are owned by their lists
+            (Some(this), Some(other)) => unsafe { this.as_ref() == other.as_ref() },
+            (None, None) => true,
+            (_, _) => false
+        }
+    }
+}
+
+impl<T: ?Sized + Eq, A: AllocRef> Eq for LinkedList<T, A> {}
+
+impl<T: ?Sized + PartialOrd, A: AllocRef> PartialOrd for LinkedList<T, A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: ?Sized + Ord, A: AllocRef> Ord for LinkedList<T, A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: ?Sized + Hash, A: AllocRef> Hash for LinkedList<T, A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        if let Some(head) = self.head {
+            // SAFETY: 'head' is owned by the list
+            unsafe { head.as_ref() }.hash(state)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn collect_and_extend() {
+        let mut list: LinkedList<_> = (1..=3).collect();
+        list.extend(&[4, 5]);
+
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.tail(), Some(&5));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5])
+    }
+
+    #[test]
+    fn clone_and_compare() {
+        let list: LinkedList<_> = vec!["a", "b", "c"].into_iter().collect();
+        let copy = list.clone();
+        assert_eq!(list, copy);
+        assert_eq!(copy.tail(), Some(&"c"));
+
+        let shorter: LinkedList<_> = vec!["a", "b"].into_iter().collect();
+        assert_ne!(list, shorter);
+        assert!(shorter < list);
+        assert!(list > LinkedList::default());
+
+        let bigger: LinkedList<_> = vec!["a", "c"].into_iter().collect();
+        assert_eq!(list.cmp(&bigger), Ordering::Less)
+    }
+
+    #[test]
+    fn hash_set_of_lists() {
+        let mut set = HashSet::new();
+        set.insert((0..4).collect::<LinkedList<_>>());
+        set.insert((0..4).collect::<LinkedList<_>>());
+        set.insert((0..3).collect::<LinkedList<_>>());
+        set.insert(LinkedList::new());
+
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&(0..3).collect::<LinkedList<_>>()))
+    }
+
+    #[test]
+    fn unsized_equality() {
+        let mut list = LinkedList::<str>::new();
+        list.push_tail_boxed("unsized".into());
+        let mut other = LinkedList::<str>::default();
+        other.push_head_boxed("unsized".into());
+        assert_eq!(list, other);
+
+        other.push_head_boxed("".into());
+        assert_ne!(list, other)
+    }
+}
@@ -0,0 +1,187 @@
+use super::list::LinkedList;
+use super::node::Link;
+
+use std::alloc::AllocRef;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+
+/// Structural problem found by [`LinkedList::check_invariants`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvariantError {
+    /// Following `next` from the head never reaches a `None`. `nodes` is
+    /// how many nodes were visited when the cycle was detected.
+    Cycle { nodes: usize },
+    /// The tail node has a `next` node.
+    TailHasNext,
+    /// `tail` does not point at the last node reachable from the head.
+    TailMismatch,
+    /// The stored length differs from the number of nodes.
+    LengthMismatch { length: usize, nodes: usize }
+}
+
+impl Display for InvariantError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Cycle { nodes } => write!(f, "cycle detected after {} nodes", nodes),
+            Self::TailHasNext => write!(f, "tail node links to another node"),
+            Self::TailMismatch => write!(f, "tail does not point at the last node"),
+            Self::LengthMismatch { length, nodes } => {
+                write!(f, "length is {}, but the list has {} nodes", length, nodes)
+            },
+        }
+    }
+}
+
+impl Error for InvariantError {}
+
+#[inline]
+fn next<T: ?Sized>(link: Link<T>) -> Link<T> {
+    // SAFETY: links in a list are either 'None' or valid nodes, even
+    // when the structure around them is broken
+    link.and_then(|ptr| unsafe { ptr.as_ref() }.next)
+}
+
+impl<T: ?Sized, A: AllocRef> LinkedList<T, A> {
+    /// Walks the whole list, checking that the links, the tail pointer
+    /// and the length are consistent.
+    ///
+    /// Cycles are detected by moving a second pointer at half the speed,
+    /// so this always terminates, in *O*(*n*).
+    ///
+    /// Only available with the `invariants` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first broken invariant, see [`InvariantError`].
+    #[inline]
+    pub fn check_invariants(&self) -> std::result::Result<(), InvariantError> {
+        let (mut last, mut slow, mut fast) = (None, self.head, self.head);
+        let mut nodes = 0;
+
+        while fast.is_some() {
+            last = fast;
+            fast = next(fast);
+            nodes += 1;
+
+            if nodes % 2 == 0 {
+                slow = next(slow)
+            }
+            if fast.is_some() && fast == slow {
+                return Err(InvariantError::Cycle { nodes })
+            }
+        }
+
+        if next(self.tail).is_some() {
+            return Err(InvariantError::TailHasNext)
+        }
+        if self.tail != last {
+            return Err(InvariantError::TailMismatch)
+        }
+        if self.length != nodes {
+            return Err(InvariantError::LengthMismatch { length: self.length, nodes })
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::XorShift;
+    use std::collections::VecDeque;
+
+    fn assert_matches(list: &LinkedList<u64>, model: &VecDeque<u64>) {
+        assert_eq!(list.check_invariants(), Ok(()));
+        assert_eq!(list.len(), model.len());
+        assert_eq!(list.tail(), model.back());
+        assert!(list.iter().eq(model.iter()))
+    }
+
+    #[test]
+    fn detects_broken_lists() {
+        let mut list: LinkedList<_> = (0..5).collect();
+        assert_eq!(list.check_invariants(), Ok(()));
+
+        list.length = 4;
+        let mismatch = InvariantError::LengthMismatch { length: 4, nodes: 5 };
+        assert_eq!(list.check_invariants(), Err(mismatch));
+        list.length = 5;
+
+        let (head, tail) = (list.head, list.tail);
+        list.tail = head;
+        assert_eq!(list.check_invariants(), Err(InvariantError::TailHasNext));
+        list.tail = None;
+        assert_eq!(list.check_invariants(), Err(InvariantError::TailMismatch));
+        list.tail = tail;
+
+        // link the tail back to the second node
+        if let Some(ptr) = tail {
+            // SAFETY: 'tail' is owned by the list
+            unsafe { (*ptr.as_ptr()).next = next(head) }
+        }
+        assert!(matches!(list.check_invariants(), Err(InvariantError::Cycle { .. })));
+        let message = list.check_invariants().unwrap_err().to_string();
+        assert!(message.starts_with("cycle "));
+
+        if let Some(ptr) = tail {
+            // SAFETY: 'tail' is owned by the list
+            unsafe { (*ptr.as_ptr()).next = None }
+        }
+        assert_eq!(list.check_invariants(), Ok(()))
+    }
+
+    #[test]
+    fn random_operations_against_model() {
+        for seed in 1..=200 {
+            let mut rng = XorShift::new(seed);
+            let mut list = LinkedList::new();
+            let mut model = VecDeque::new();
+
+            for _ in 0..200 {
+                let value = rng.next_u64() % 1000;
+                match rng.below(8) {
+                    0 | 1 => {
+                        list.push_head(value);
+                        model.push_front(value)
+                    },
+                    2 | 3 => {
+                        list.push_tail(value);
+                        model.push_back(value)
+                    },
+                    4 | 5 => assert_eq!(list.pop(), model.pop_front()),
+                    6 => {
+                        let at = rng.below(model.len() + 1);
+                        let mut rest = list.split_off(at);
+                        let model_rest = model.split_off(at);
+                        assert_matches(&rest, &model_rest);
+
+                        list.append(&mut rest);
+                        model.extend(model_rest)
+                    },
+                    _ => {
+                        let mut cursor = list.cursor_front_mut();
+                        let at = rng.below(model.len() + 1);
+                        for _ in 0..at {
+                            cursor.move_next()
+                        }
+                        if rng.below(2) == 0 {
+                            assert_eq!(cursor.remove_current(), model.remove(at))
+                        } else {
+                            // at the ghost position, this goes to the front
+                            cursor.insert_after(value);
+                            let index = if at == model.len() { 0 } else { at + 1 };
+                            model.insert(index, value)
+                        }
+                    },
+                }
+                assert_matches(&list, &model)
+            }
+
+            let mut sorted: Vec<_> = model.into_iter().collect();
+            sorted.sort_unstable();
+            list.sort();
+            assert_matches(&list, &sorted.into())
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use super::list::LinkedList;
+use super::node::{Link, Node};
+
+use std::alloc::{AllocRef, Global};
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// Borrowing iterator over the elements of a [`LinkedList`].
+///
+/// Created by [`LinkedList::iter`].
+pub struct Iter<'a, T: ?Sized> {
+    pub(super) next: Option<&'a Node<T>>,
+    pub(super) length: usize
+}
+
+// SAFETY: behaves like a '&T'
+unsafe impl<T: ?Sized + Sync> Send for Iter<'_, T> {}
+// SAFETY: behaves like a '&T'
+unsafe impl<T: ?Sized + Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next_ref();
+            self.length -= 1;
+            &node.data
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { next: self.next, length: self.length }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for Iter<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// Mutable borrowing iterator over the elements of a [`LinkedList`].
+///
+/// Created by [`LinkedList::iter_mut`].
+pub struct IterMut<'a, T: ?Sized> {
+    pub(super) next: Link<T>,
+    pub(super) length: usize,
+    pub(super) marker: PhantomData<&'a mut Node<T>>
+}
+
+// SAFETY: behaves like a '&mut T'
+unsafe impl<T: ?Sized + Send> Send for IterMut<'_, T> {}
+// SAFETY: behaves like a '&mut T'
+unsafe impl<T: ?Sized + Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T: ?Sized> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.next.take().map(|ptr| {
+            // SAFETY: the list is mutably borrowed, and each node
+            // is yielded only once
+            let node = unsafe { &mut *ptr.as_ptr() };
+            self.next = node.next;
+            self.length -= 1;
+            &mut node.data
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for IterMut<'_, T> {}
+
+impl<T: ?Sized + Debug> Debug for IterMut<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("IterMut").field("length", &self.length).finish()
+    }
+}
+
+/// Owning iterator over the elements of a [`LinkedList`].
+///
+/// Created by [`LinkedList::into_iter`](IntoIterator::into_iter).
+#[derive(Debug)]
+pub struct IntoIter<T, A: AllocRef = Global> {
+    pub(super) list: LinkedList<T, A>
+}
+
+impl<T, A: AllocRef> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T, A: AllocRef> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: AllocRef> FusedIterator for IntoIter<T, A> {}
+
+/// Owning iterator over the boxed elements of a [`LinkedList`], for
+/// possibly unsized `T`.
+///
+/// Created by [`LinkedList::into_iter_boxed`].
+#[derive(Debug)]
+pub struct IntoIterBoxed<T: ?Sized, A: AllocRef = Global> {
+    pub(super) list: LinkedList<T, A>
+}
+
+impl<T: ?Sized, A: AllocRef> Iterator for IntoIterBoxed<T, A> {
+    type Item = Box<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Box<T>> {
+        self.list.pop_boxed()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T: ?Sized, A: AllocRef> ExactSizeIterator for IntoIterBoxed<T, A> {}
+
+impl<T: ?Sized, A: AllocRef> FusedIterator for IntoIterBoxed<T, A> {}
+
+impl<T: ?Sized, A: AllocRef> LinkedList<T, A> {
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        // SAFETY: 'head' is a node owned by the list
+        let next = self.head.map(|ptr| unsafe { &*ptr.as_ptr() });
+        Iter { next, length: self.length }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.head, length: self.length, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn into_iter_boxed(self) -> IntoIterBoxed<T, A> {
+        IntoIterBoxed { list: self }
+    }
+}
+
+impl<T, A: AllocRef> IntoIterator for LinkedList<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T, A> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T: ?Sized, A: AllocRef> IntoIterator for &'a LinkedList<T, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T: ?Sized, A: AllocRef> IntoIterator for &'a mut LinkedList<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowing_keeps_list() {
+        let mut list = LinkedList::new();
+        list.push_tail(1);
+        list.push_tail(2);
+        list.push_tail(3);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &3]);
+
+        for elem in &mut list {
+            *elem *= 10
+        }
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![10, 20, 30])
+    }
+
+    #[test]
+    fn fused_at_end() {
+        let mut list = LinkedList::new();
+        list.push_head(1);
+
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 1));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.len(), 0)
+    }
+
+    #[test]
+    fn unsized_elements() {
+        let mut list = LinkedList::<str>::new();
+        list.push_tail_boxed("hello".into());
+        list.push_tail_boxed("world".into());
+
+        let words: Vec<_> = list.iter().collect();
+        assert_eq!(words, vec!["hello", "world"]);
+
+        list.iter_mut().for_each(str::make_ascii_uppercase);
+        let boxed: Vec<_> = list.into_iter_boxed().collect();
+        assert_eq!(boxed, vec!["HELLO".into(), "WORLD".into()] as Vec<Box<str>>)
+    }
+}
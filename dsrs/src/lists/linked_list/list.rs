@@ -1,19 +1,58 @@
-use super::node::Node;
+use super::node::{Link, Node};
 
 use mem::ptr::NonNull;
 
-#[derive(Debug)]
-pub struct LinkedList<T: ?Sized> {
-    pub(super) head: Option<Box<Node<T>>>,
-    pub(super) tail: Option<NonNull<Node<T>>>,
-    pub(super) length: usize
+use std::alloc::{AllocRef, Global};
+use std::fmt::{Debug, Formatter, Result};
+use std::marker::PhantomData;
+
+pub struct LinkedList<T: ?Sized, A: AllocRef = Global> {
+    pub(super) head: Link<T>,
+    pub(super) tail: Link<T>,
+    pub(super) length: usize,
+    pub(super) alloc: A,
+    pub(super) marker: PhantomData<Box<Node<T>>>
 }
 
+/// Allocators whose values can all release the blocks of each other.
+///
+/// Moving nodes from one list to another, as [`LinkedList::append`] does,
+/// needs this, since each list frees its nodes with its own allocator.
+///
+/// # Safety
+///
+/// Any block allocated by a value of the type must be valid to deallocate
+/// with any other value of it.
+pub unsafe trait SharedAlloc: AllocRef {}
+
+// SAFETY: there is a single global allocator
+unsafe impl SharedAlloc for Global {}
+
+// SAFETY: the list owns its nodes, just like a 'Box'
+unsafe impl<T: ?Sized + Send, A: AllocRef + Send> Send for LinkedList<T, A> {}
+// SAFETY: shared references only give shared access to the nodes
+unsafe impl<T: ?Sized + Sync, A: AllocRef + Sync> Sync for LinkedList<T, A> {}
+
 impl<T: ?Sized> LinkedList<T> {
     #[must_use]
     #[inline]
     pub const fn new() -> Self {
-        Self { head: None, tail: None, length: 0 }
+        Self::new_in(Global)
+    }
+}
+
+impl<T: ?Sized, A: AllocRef> LinkedList<T, A> {
+    /// Creates an empty list, whose nodes will be allocated with `alloc`.
+    #[must_use]
+    #[inline]
+    pub const fn new_in(alloc: A) -> Self {
+        Self { head: None, tail: None, length: 0, alloc, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn allocator(&self) -> &A {
+        &self.alloc
     }
 
     #[must_use]
@@ -28,6 +67,33 @@ impl<T: ?Sized> LinkedList<T> {
         self.head.is_none()
     }
 
+    #[must_use]
+    #[inline]
+    pub const fn head(&self) -> Option<&T> {
+        match self.head {
+            None => None,
+            Some(ref ptr) => {
+                // SAFETY: ptr is a valid reference
+                let head = unsafe { ptr.as_ref() };
+                Some(&head.data)
+            }
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn head_mut(&mut self) -> Option<&mut T> {
+        match self.head {
+            None => None,
+            Some(ref mut ptr) => {
+                // SAFETY: 'ptr' is a valid reference and the
+                // first node can't be accessed elsewhere
+                let head = unsafe { ptr.as_mut() };
+                Some(&mut head.data)
+            }
+        }
+    }
+
     #[must_use]
     #[inline]
     pub const fn tail(&self) -> Option<&T> {
@@ -56,29 +122,37 @@ impl<T: ?Sized> LinkedList<T> {
     }
 }
 
-impl<T: ?Sized> LinkedList<T> {
+impl<T: ?Sized, A: AllocRef> LinkedList<T, A> {
+    /// # Safety
+    ///
+    /// `node` must be allocated with the list allocator and
+    /// `node.next` must be `None`.
     #[inline]
-    unsafe fn push_node_head(&mut self, mut node: Box<Node<T>>) {
-        if let Some(next) = self.head.take() {
-            // SAFETY: caller must ensure that 'node.next' is
-            // currently 'None'
-            unsafe { node.push_next_unchecked(next) }
+    pub(super) unsafe fn push_node_head(&mut self, node: NonNull<Node<T>>) {
+        // SAFETY: caller must ensure that 'node' is valid
+        let node_ref = unsafe { &mut *node.as_ptr() };
+        debug_assert!(node_ref.next.is_none());
 
-        } else {
+        if self.head.is_none() {
             debug_assert!(self.tail.is_none());
-            self.tail = Some(node.as_ptr())
+            self.tail = Some(node)
         }
-        self.head = Some(node);
+        node_ref.next = self.head.replace(node);
         self.length += 1
     }
 
+    /// # Safety
+    ///
+    /// `node` must be allocated with the list allocator and
+    /// `node.next` must be `None`.
     #[inline]
-    unsafe fn push_node_tail(&mut self, node: Box<Node<T>>) {
-        if let Some(mut ptr) = self.tail.replace(node.as_ptr()) {
-            // SAFETY: 'tail' is a valid mutable reference
-            let tail = unsafe { ptr.as_mut() };
-            // SAFETY: 'tail.next' is None
-            unsafe { tail.push_next_unchecked(node) }
+    pub(in crate::lists) unsafe fn push_node_tail(&mut self, node: NonNull<Node<T>>) {
+        // SAFETY: caller must ensure that 'node' is valid
+        debug_assert!(unsafe { node.as_ref() }.next.is_none());
+
+        if let Some(ptr) = self.tail.replace(node) {
+            // SAFETY: 'tail' is a node owned by the list
+            unsafe { (*ptr.as_ptr()).next = Some(node) }
 
         } else {
             debug_assert!(self.head.is_none());
@@ -89,63 +163,352 @@ impl<T: ?Sized> LinkedList<T> {
 
     #[inline]
     pub fn push_head_boxed(&mut self, data: Box<T>) {
-        // SAFETY: next is None
-        unsafe { self.push_node_head(Node::build(data, None)) }
+        let node = Node::build_in(data, None, &mut self.alloc);
+        // SAFETY: allocated with the list allocator and next is None
+        unsafe { self.push_node_head(node) }
     }
 
     #[inline]
     pub fn push_tail_boxed(&mut self, data: Box<T>) {
-        // SAFETY: next is None
-        unsafe { self.push_node_tail(Node::build(data, None)) }
+        let node = Node::build_in(data, None, &mut self.alloc);
+        // SAFETY: allocated with the list allocator and next is None
+        unsafe { self.push_node_tail(node) }
     }
 
+    /// Unlinks the head node, whose ownership goes to the caller.
     #[must_use]
     #[inline]
-    fn pop_node(&mut self) -> Option<Box<Node<T>>> {
-        self.head.take().map(|mut node| {
-            self.head = node.next.take();
+    pub(super) fn pop_node(&mut self) -> Link<T> {
+        self.head.map(|ptr| {
+            // SAFETY: 'head' is a node owned by the list
+            self.head = unsafe { &mut *ptr.as_ptr() }.next.take();
             if self.head.is_none() {
                 self.tail = None
             }
             self.length -= 1;
-            node
+            ptr
         })
     }
 
     #[must_use]
     #[inline]
     pub fn pop_boxed(&mut self) -> Option<Box<T>> {
-        self.pop_node().map(|x| x.split().0)
+        let node = self.pop_node()?;
+        // SAFETY: the node was unlinked and belongs to the list allocator
+        Some(unsafe { Node::split_in(node, &mut self.alloc) }.0)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        let mut next = self.head.take();
+        self.tail = None;
+        self.length = 0;
+
+        // free nodes one at a time, 'Node::next'
+        // is never followed on drop
+        while let Some(ptr) = next {
+            // SAFETY: every node is owned by the list and is only
+            // visited once
+            next = unsafe { &mut *ptr.as_ptr() }.next.take();
+            // SAFETY: the node was unlinked above
+            unsafe { Node::free_in(ptr, &mut self.alloc) }
+        }
+    }
+}
+
+impl<T: ?Sized, A: SharedAlloc> LinkedList<T, A> {
+    /// Moves all elements of `other` to the end of the list, leaving
+    /// `other` empty.
+    ///
+    /// This is *O*(1), using the tail pointer.
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        let (head, tail) = match (other.head.take(), other.tail.take()) {
+            (Some(head), Some(tail)) => (head, tail),
+            (_, _) => return
+        };
+
+        match self.tail.replace(tail) {
+            // SAFETY: 'last' is the tail, so 'last.next' is None, and the
+            // nodes of 'other' can be released by this list allocator
+            Some(last) => unsafe { (*last.as_ptr()).next = Some(head) },
+            None => self.head = Some(head)
+        }
+        self.length += std::mem::replace(&mut other.length, 0)
+    }
+
+    /// Moves all elements of `other` to the start of the list, leaving
+    /// `other` empty.
+    ///
+    /// This is *O*(1), using the tail pointer of `other`.
+    #[inline]
+    pub fn prepend(&mut self, other: &mut Self) {
+        other.append(self);
+        std::mem::swap(self, other)
+    }
+}
+
+impl<T: ?Sized, A: AllocRef + Clone> LinkedList<T, A> {
+    /// Splits the list in two at the given index. Returns everything after
+    /// the given index, including the index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > self.len()`.
+    #[must_use]
+    #[inline]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "cannot split off at a nonexistent index");
+        if at == 0 {
+            let empty = Self::new_in(self.alloc.clone());
+            return std::mem::replace(self, empty)
+        }
+
+        let mut node = self.head;
+        for _ in 1..at {
+            // SAFETY: every node is owned by the list
+            node = node.and_then(|ptr| unsafe { ptr.as_ref() }.next)
+        }
+        let last = match node {
+            Some(last) => last,
+            None => unreachable!("length must match the number of nodes")
+        };
+
+        // SAFETY: 'last' is a node owned by the list
+        let head = unsafe { &mut *last.as_ptr() }.next.take();
+        let tail = match head {
+            None => None,
+            Some(_) => self.tail.replace(last)
+        };
+        let length = self.length - at;
+        self.length = at;
+
+        Self { head, tail, length, alloc: self.alloc.clone(), marker: PhantomData }
+    }
+
+    /// Splits the list before the first element that matches `pred`.
+    /// Returns that element and everything after it, or an empty list
+    /// if no element matches.
+    #[must_use]
+    #[inline]
+    pub fn split_when<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Self {
+        let mut cursor = self.cursor_front_mut();
+        if cursor.current().map_or(true, |head| pred(head)) {
+            return self.split_off(0)
+        }
+
+        while let Some(next) = cursor.peek_next() {
+            if pred(next) {
+                return cursor.split_after()
+            }
+            cursor.move_next()
+        }
+        Self::new_in(self.alloc.clone())
     }
 }
 
-impl<T> LinkedList<T> {
+impl<T, A: AllocRef> LinkedList<T, A> {
     #[inline]
     pub fn push_head(&mut self, data: T) {
-        let node = Box::new(Node { next: None, data });
-        // SAFETY: next is None
+        let node = Node::new_in(data, None, &mut self.alloc);
+        // SAFETY: allocated with the list allocator and next is None
         unsafe { self.push_node_head(node) }
     }
 
     #[inline]
     pub fn push_tail(&mut self, data: T) {
-        let node = Box::new(Node { next: None, data });
-        // SAFETY: next is None
+        let node = Node::new_in(data, None, &mut self.alloc);
+        // SAFETY: allocated with the list allocator and next is None
         unsafe { self.push_node_tail(node) }
     }
 
     #[must_use]
     #[inline]
     pub fn pop(&mut self) -> Option<T> {
-        self.pop_node().map(|node| node.data)
+        let node = self.pop_node()?;
+        // SAFETY: the node was unlinked and belongs to the list allocator
+        Some(unsafe { Node::take_in(node, &mut self.alloc) }.0)
     }
 }
 
-impl<T> Iterator for LinkedList<T> {
-    type Item = T;
+impl<T: ?Sized, A: AllocRef> Drop for LinkedList<T, A> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
 
+impl<T: ?Sized + Debug, A: AllocRef> Debug for LinkedList<T, A> {
     #[inline]
-    fn next(&mut self) -> Option<T> {
-        self.pop()
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{AllocErr, Layout};
+    use std::cell::Cell;
+    use std::ptr::NonNull as RawNonNull;
+    use std::rc::Rc;
+
+    /// Global allocator that counts live allocations.
+    #[derive(Clone, Default)]
+    struct Counting(Rc<Cell<usize>>);
+
+    // SAFETY: every block comes from 'Global'
+    unsafe impl AllocRef for Counting {
+        fn alloc(&mut self, layout: Layout) -> std::result::Result<RawNonNull<[u8]>, AllocErr> {
+            self.0.set(self.0.get() + 1);
+            Global.alloc(layout)
+        }
+
+        unsafe fn dealloc(&mut self, ptr: RawNonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            // SAFETY: guaranteed by the caller
+            unsafe { Global.dealloc(ptr, layout) }
+        }
+    }
+
+    // SAFETY: every block is released to 'Global'
+    unsafe impl SharedAlloc for Counting {}
+
+    fn contents<T: Copy>(list: &LinkedList<T>) -> Vec<T> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn append_and_prepend() {
+        let mut list: LinkedList<_> = (0..3).collect();
+        let mut other: LinkedList<_> = (3..5).collect();
+
+        list.append(&mut other);
+        assert!(other.is_empty() && other.tail().is_none());
+        assert_eq!((list.len(), list.tail()), (5, Some(&4)));
+
+        list.append(&mut LinkedList::new());
+        other.append(&mut list);
+        assert_eq!((list.len(), list.tail()), (0, None));
+        assert_eq!((other.len(), other.tail()), (5, Some(&4)));
+
+        let mut before: LinkedList<_> = (-2..0).collect();
+        other.prepend(&mut before);
+        assert!(before.is_empty());
+        assert_eq!(contents(&other), (-2..5).collect::<Vec<_>>());
+        assert_eq!(other.tail(), Some(&4))
+    }
+
+    #[test]
+    fn split_off_and_split_when() {
+        let mut list: LinkedList<_> = (0..6).collect();
+
+        let rest = list.split_off(4);
+        assert_eq!((contents(&rest), rest.tail()), (vec![4, 5], Some(&5)));
+        assert_eq!((list.len(), list.tail()), (4, Some(&3)));
+        assert!(list.split_off(4).is_empty());
+        assert_eq!(list.tail(), Some(&3));
+
+        let rest = list.split_when(|&x| x > 1);
+        assert_eq!((contents(&rest), rest.tail(), rest.len()), (vec![2, 3], Some(&3), 2));
+        assert_eq!((contents(&list), list.tail(), list.len()), (vec![0, 1], Some(&1), 2));
+        assert!(list.split_when(|&x| x > 1).is_empty());
+
+        let mut all = list.split_when(|_| true);
+        assert_eq!((all.len(), list.len(), list.tail()), (2, 0, None));
+        assert_eq!(all.split_off(0).len(), 2)
+    }
+
+    #[test]
+    fn unsized_bulk_operations() {
+        let mut list = LinkedList::<[u8]>::new();
+        let mut other = LinkedList::<[u8]>::new();
+        list.push_tail_boxed(Box::new([1]));
+        other.push_tail_boxed(Box::new([2, 2]));
+        other.push_tail_boxed(Box::new([3, 3, 3]));
+
+        list.append(&mut other);
+        let rest = list.split_when(|data| data.len() > 2);
+        assert_eq!((list.len(), list.tail()), (2, Some(&[2, 2][..])));
+        assert_eq!((rest.len(), rest.tail()), (1, Some(&[3, 3, 3][..])))
+    }
+
+    #[test]
+    #[should_panic(expected = "nonexistent index")]
+    fn split_off_out_of_bounds() {
+        let mut list: LinkedList<_> = (0..2).collect();
+        let _ = list.split_off(3);
+    }
+
+    #[test]
+    fn custom_allocator() {
+        let alloc = Counting::default();
+        let mut list = LinkedList::new_in(alloc.clone());
+        list.extend(0..10);
+        let mut words = LinkedList::<str, _>::new_in(alloc.clone());
+        words.push_tail_boxed("boxed".into());
+        assert_eq!(alloc.0.get(), 11);
+
+        let rest = list.split_off(5);
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!((list.pop(), rest.tail()), (Some(4), Some(&9)));
+        assert_eq!(words.pop_boxed().as_deref(), Some("boxed"));
+        assert_eq!(alloc.0.get(), 9);
+
+        let copy = rest.clone();
+        assert_eq!((alloc.0.get(), copy), (14, rest));
+        drop((list, words));
+        assert_eq!(alloc.0.get(), 0)
+    }
+
+    #[test]
+    fn bulk_operations_in_allocator() {
+        let alloc = Counting::default();
+        let mut list = LinkedList::new_in(alloc.clone());
+        list.extend(2..4);
+        let mut other = LinkedList::new_in(alloc.clone());
+        other.extend(4..6);
+        list.append(&mut other);
+        other.extend(0..2);
+        list.prepend(&mut other);
+        assert_eq!((list.len(), list.tail(), other.len()), (6, Some(&5), 0));
+
+        other.extend(8..10);
+        list.cursor_front_mut().splice_after(other);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 8, 9, 1, 2, 3, 4, 5]);
+        assert_eq!((list.tail(), alloc.0.get()), (Some(&5), 8));
+        drop(list);
+        assert_eq!(alloc.0.get(), 0)
+    }
+
+    #[test]
+    fn drop_long_list() {
+        let mut list = LinkedList::new();
+        for i in 0..10_000_000_u32 {
+            list.push_head(i)
+        }
+        assert_eq!(list.len(), 10_000_000);
+        drop(list)
+    }
+
+    #[test]
+    fn drop_long_unsized_list() {
+        let mut list = LinkedList::<str>::new();
+        for i in 0..1_000_000 {
+            list.push_tail_boxed(i.to_string().into_boxed_str())
+        }
+        assert_eq!(list.tail(), Some("999999"));
+        drop(list)
+    }
+
+    #[test]
+    fn clone_long_list() {
+        let mut list = LinkedList::new();
+        for i in 0..1_000_000_u32 {
+            list.push_head(i)
+        }
+        let copy = list.clone();
+        assert_eq!(copy, list);
+        assert_eq!(copy.tail(), Some(&0))
     }
 }
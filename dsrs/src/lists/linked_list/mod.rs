@@ -1,5 +1,14 @@
-mod node;
+pub(super) mod node;
 mod list;
+mod iter;
+mod cursor;
+mod sort;
 mod impls;
+#[cfg(any(test, feature = "invariants"))]
+mod invariants;
 
-pub use list::LinkedList;
+pub use list::{LinkedList, SharedAlloc};
+pub use iter::{IntoIter, IntoIterBoxed, Iter, IterMut};
+pub use cursor::{Cursor, CursorMut};
+#[cfg(any(test, feature = "invariants"))]
+pub use invariants::InvariantError;
@@ -1,41 +1,86 @@
-use mem::alloc::ReprC;
+use mem::alloc::{Layout, ReprC};
 use mem::ptr::NonNull;
 use mem::ReprC;
 
+use std::alloc::{handle_alloc_error, AllocRef};
 use std::hash::{Hash, Hasher};
 
-type Next<T> = Option<Box<Node<T>>>;
+pub(in crate::lists) type Link<T> = Option<NonNull<Node<T>>>;
 
 #[repr(C)]
-#[derive(Debug, Clone, ReprC)]
-pub(super) struct Node<T: ?Sized> {
-    pub next: Next<T>,
+#[derive(Debug, ReprC)]
+pub(in crate::lists) struct Node<T: ?Sized> {
+    pub next: Link<T>,
     pub data: T
 }
 
 impl<T: ?Sized> Node<T> {
     #[inline]
-    pub fn build(data: Box<T>, next: Next<T>) -> Box<Self> {
-        ReprC::expand(data, (next,))
+    pub fn build_in<A: AllocRef>(data: Box<T>, next: Link<T>, alloc: &mut A) -> NonNull<Self> {
+        ReprC::expand_in(data, (next,), alloc)
     }
 
+    /// # Safety
+    ///
+    /// `ptr` must be a node allocated with `alloc` and not linked anywhere.
     #[inline]
-    pub fn split(self: Box<Self>) -> (Box<T>, Next<T>) {
-        let (data, (next,)) = ReprC::split(self, true);
+    pub unsafe fn split_in<A: AllocRef>(ptr: NonNull<Self>, alloc: &mut A) -> (Box<T>, Link<T>) {
+        // SAFETY: guaranteed by the caller
+        let (data, (next,)) = unsafe { ReprC::split_in(ptr, alloc) };
         (data, next)
     }
 
+    /// Drops the node data and releases its memory, without following
+    /// `next`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a node allocated with `alloc` and not linked anywhere.
     #[inline]
-    pub const fn as_ptr(&self) -> NonNull<Self> {
-        NonNull::from(self)
+    pub unsafe fn free_in<A: AllocRef>(ptr: NonNull<Self>, alloc: &mut A) {
+        // SAFETY: caller guarantees that 'ptr' is a valid node
+        let layout = Layout::for_value(unsafe { ptr.as_ref() });
+        // SAFETY: the node is dropped only once and then released
+        // with the same allocator
+        unsafe {
+            std::ptr::drop_in_place(ptr.as_ptr());
+            alloc.dealloc(ptr.cast().inner(), layout.inner())
+        }
     }
 
     #[inline]
-    pub unsafe fn push_next_unchecked(&mut self, next: Box<Self>) {
-        debug_assert!(self.next.is_none());
-        // SAFETY: write is valid, since we take it by reference
-        // but caller must guarantee that drop is not necessary
-        unsafe { std::ptr::write(&mut self.next, Some(next)) }
+    pub fn next_ref(&self) -> Option<&Self> {
+        // SAFETY: nodes are only reachable through the list that owns
+        // the whole chain, so 'next' lives as long as 'self'
+        self.next.map(|ptr| unsafe { &*ptr.as_ptr() })
+    }
+}
+
+impl<T> Node<T> {
+    #[inline]
+    pub fn new_in<A: AllocRef>(data: T, next: Link<T>, alloc: &mut A) -> NonNull<Self> {
+        let layout = Layout::new::<Self>();
+        let ptr: NonNull<Self> = match alloc.alloc(layout.inner()) {
+            Err(_) => handle_alloc_error(layout.inner()),
+            Ok(ptr) => NonNull::from_inner(ptr.cast())
+        };
+        // SAFETY: 'ptr' was just allocated for a 'Node<T>'
+        unsafe { ptr.as_ptr().write(Self { next, data }) };
+        ptr
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must be a node allocated with `alloc` and not linked anywhere.
+    #[inline]
+    pub unsafe fn take_in<A: AllocRef>(ptr: NonNull<Self>, alloc: &mut A) -> (T, Link<T>) {
+        // SAFETY: caller guarantees that 'ptr' is a valid node, which
+        // is read only once and then released with the same allocator
+        unsafe {
+            let Self { next, data } = ptr.as_ptr().read();
+            alloc.dealloc(ptr.cast().inner(), Layout::new::<Self>().inner());
+            (data, next)
+        }
     }
 }
 
@@ -45,23 +90,23 @@ impl<T: ?Sized + Hash> Hash for Node<T> {
         self.data.hash(state);
 
         // iteratively hash everything
-        let mut next = &self.next;
+        let mut next = self.next_ref();
         while let Some(node) = next {
             node.data.hash(state);
 
-            next = &node.next
+            next = node.next_ref()
         }
     }
 }
 
-impl<U, T: ?Sized + PartialEq<U>> PartialEq<Node<U>> for Node<T> {
+impl<U: ?Sized, T: ?Sized + PartialEq<U>> PartialEq<Node<U>> for Node<T> {
     #[inline]
     fn eq(&self, other: &Node<U>) -> bool {
         if self.data != other.data {
             return false
         }
 
-        let (mut this, mut other) = (&self.next, &other.next);
+        let (mut this, mut other) = (self.next_ref(), other.next_ref());
         loop {
             let (next_this, next_other) = match (this, other) {
                 (Some(this), Some(other)) => (this, other),
@@ -72,8 +117,8 @@ impl<U, T: ?Sized + PartialEq<U>> PartialEq<Node<U>> for Node<T> {
             if next_this.data != next_other.data {
                 break false
             }
-            this = &next_this.next;
-            other = &next_other.next;
+            this = next_this.next_ref();
+            other = next_other.next_ref();
         }
     }
 }
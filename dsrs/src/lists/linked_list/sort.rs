@@ -0,0 +1,342 @@
+use super::list::LinkedList;
+use super::node::{Link, Node};
+
+use mem::ptr::NonNull;
+
+use std::alloc::AllocRef;
+use std::cmp::Ordering;
+
+/// Detaches the first `len` nodes from `list`, returning them along with
+/// the rest of the list.
+///
+/// # Safety
+///
+/// `list` must be a valid chain of nodes.
+#[inline]
+unsafe fn split_run<T: ?Sized>(list: Link<T>, len: usize) -> (Link<T>, Link<T>) {
+    let mut end = match list {
+        Some(ptr) => ptr,
+        None => return (None, None)
+    };
+    for _ in 1..len {
+        // SAFETY: 'end' is in the chain
+        match unsafe { end.as_ref() }.next {
+            Some(next) => end = next,
+            None => return (list, None)
+        }
+    }
+    // SAFETY: 'end' is in the chain
+    let rest = unsafe { &mut *end.as_ptr() }.next.take();
+    (list, rest)
+}
+
+/// State of a running sort, owning every node of the list.
+///
+/// Each pass merges pairs of runs taken from `rest` onto the end of
+/// `sorted`. Dropping it links the chains back into the list, so that
+/// a panicking comparator still leaves it valid.
+struct Sorting<'a, T: ?Sized, A: AllocRef> {
+    list: &'a mut LinkedList<T, A>,
+    sorted: Link<T>,
+    end: Link<T>,
+    left: Link<T>,
+    right: Link<T>,
+    rest: Link<T>
+}
+
+impl<T: ?Sized, A: AllocRef> Sorting<'_, T, A> {
+    /// Appends `node` to the sorted chain.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be detached from the other chains and its next must
+    /// be `None`.
+    #[inline]
+    unsafe fn push(&mut self, node: NonNull<Node<T>>) {
+        match self.end {
+            // SAFETY: 'prev' is the end of the sorted chain
+            Some(prev) => unsafe { (*prev.as_ptr()).next = Some(node) },
+            None => self.sorted = Some(node)
+        }
+        self.end = Some(node)
+    }
+
+    /// Stable merge of the `left` and `right` runs onto the end of the
+    /// sorted chain.
+    #[inline]
+    fn merge<F: FnMut(&T, &T) -> Ordering>(&mut self, cmp: &mut F) {
+        loop {
+            let take_left = match (self.left, self.right) {
+                // SAFETY: both are in their chains
+                (Some(a), Some(b)) => unsafe {
+                    cmp(&a.as_ref().data, &b.as_ref().data) != Ordering::Greater
+                },
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break
+            };
+            let source = if take_left { &mut self.left } else { &mut self.right };
+
+            let ptr = match *source {
+                Some(ptr) => ptr,
+                None => unreachable!("source was checked above")
+            };
+            // SAFETY: 'ptr' is in its chain, and is detached from it
+            unsafe {
+                *source = (*ptr.as_ptr()).next.take();
+                self.push(ptr)
+            }
+        }
+    }
+}
+
+impl<T: ?Sized, A: AllocRef> Drop for Sorting<'_, T, A> {
+    #[inline]
+    fn drop(&mut self) {
+        // only unwinding leaves nodes outside of the sorted chain, which
+        // are moved after it in no particular order
+        for chain in &mut [self.left.take(), self.right.take(), self.rest.take()] {
+            let mut next = chain.take();
+            while let Some(node) = next {
+                // SAFETY: 'node' is in its chain, and is detached from it
+                unsafe {
+                    next = (*node.as_ptr()).next.take();
+                    self.push(node)
+                }
+            }
+        }
+        self.list.head = self.sorted.take();
+        self.list.tail = self.end.take()
+    }
+}
+
+impl<T: ?Sized, A: AllocRef> LinkedList<T, A> {
+    /// Sorts the list with a comparator function.
+    ///
+    /// This is a stable, bottom-up merge sort that only relinks the
+    /// nodes, without any allocation. Runs in *O*(*n* log *n*).
+    ///
+    /// If `cmp` panics, the list keeps all of its elements, in an
+    /// unspecified order.
+    #[inline]
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut cmp: F) {
+        let length = self.length;
+        if length < 2 {
+            return
+        }
+        let rest = self.head.take();
+        self.tail = None;
+        let mut sorting =
+            Sorting { list: self, sorted: None, end: None, left: None, right: None, rest };
+
+        let mut width = 1;
+        loop {
+            while sorting.rest.is_some() {
+                // SAFETY: the runs are disjoint pieces of the rest chain
+                unsafe {
+                    let (left, rest) = split_run(sorting.rest.take(), width);
+                    let (right, rest) = split_run(rest, width);
+                    sorting.left = left;
+                    sorting.right = right;
+                    sorting.rest = rest
+                }
+                sorting.merge(&mut cmp)
+            }
+            width *= 2;
+            if width >= length {
+                break
+            }
+            sorting.rest = sorting.sorted.take();
+            sorting.end = None
+        }
+    }
+
+    /// Sorts the list with a key extraction function.
+    ///
+    /// See [`sort_by`](LinkedList::sort_by).
+    #[inline]
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        self.sort_by(|a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Removes and returns the first element that matches `pred`.
+    #[must_use]
+    #[inline]
+    pub fn remove_first_by_boxed<F: FnMut(&T) -> bool>(&mut self, pred: F) -> Option<Box<T>> {
+        let node = self.remove_first_node_by(pred)?;
+        // SAFETY: the node was unlinked and belongs to the list allocator
+        Some(unsafe { Node::split_in(node, &mut self.alloc) }.0)
+    }
+
+    #[inline]
+    fn remove_first_node_by<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Link<T> {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(current) = cursor.current() {
+            if pred(current) {
+                return cursor.remove_current_node()
+            }
+            cursor.move_next()
+        }
+        None
+    }
+
+    /// # Safety
+    ///
+    /// `node` must be allocated with the list allocator and
+    /// `node.next` must be `None`.
+    #[inline]
+    unsafe fn insert_node_sorted_by<F>(&mut self, node: NonNull<Node<T>>, mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // SAFETY: caller ensures that 'node' is valid
+        let data = unsafe { &(*node.as_ptr()).data };
+
+        let mut cursor = self.cursor_front_mut();
+        match cursor.current() {
+            Some(head) if cmp(data, head) != Ordering::Less => (),
+            // SAFETY: guaranteed by the caller
+            _ => return unsafe { self.push_node_head(node) }
+        }
+
+        while let Some(next) = cursor.peek_next() {
+            if cmp(next, data) == Ordering::Greater {
+                break
+            }
+            cursor.move_next()
+        }
+        // SAFETY: guaranteed by the caller
+        unsafe { cursor.insert_node_after(node) }
+    }
+
+    /// Inserts a boxed value after every element that is not greater
+    /// than it, keeping a sorted list sorted.
+    #[inline]
+    pub fn insert_sorted_by_boxed<F: FnMut(&T, &T) -> Ordering>(&mut self, data: Box<T>, cmp: F) {
+        let node = Node::build_in(data, None, &mut self.alloc);
+        // SAFETY: allocated with the list allocator and next is None
+        unsafe { self.insert_node_sorted_by(node, cmp) }
+    }
+}
+
+impl<T: ?Sized + Ord, A: AllocRef> LinkedList<T, A> {
+    /// Sorts the list in ascending order.
+    ///
+    /// See [`sort_by`](LinkedList::sort_by).
+    #[inline]
+    pub fn sort(&mut self) {
+        self.sort_by(Ord::cmp)
+    }
+}
+
+impl<T, A: AllocRef> LinkedList<T, A> {
+    /// Removes and returns the first element that matches `pred`.
+    #[must_use]
+    #[inline]
+    pub fn remove_first_by<F: FnMut(&T) -> bool>(&mut self, pred: F) -> Option<T> {
+        let node = self.remove_first_node_by(pred)?;
+        // SAFETY: the node was unlinked and belongs to the list allocator
+        Some(unsafe { Node::take_in(node, &mut self.alloc) }.0)
+    }
+
+    /// Inserts a value after every element that is not greater than it,
+    /// keeping a sorted list sorted.
+    #[inline]
+    pub fn insert_sorted_by<F: FnMut(&T, &T) -> Ordering>(&mut self, data: T, cmp: F) {
+        let node = Node::new_in(data, None, &mut self.alloc);
+        // SAFETY: allocated with the list allocator and next is None
+        unsafe { self.insert_node_sorted_by(node, cmp) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::panic::{self, AssertUnwindSafe};
+
+    fn contents<T: Copy>(list: &LinkedList<T>) -> Vec<T> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn sort_small_lists() {
+        for len in 0..40_u32 {
+            let mut list: LinkedList<_> = (0..len).map(|i| (i * 7919) % 41).collect();
+            let mut expected = contents(&list);
+            expected.sort_unstable();
+
+            list.sort();
+            assert_eq!(contents(&list), expected);
+            assert_eq!(list.len(), expected.len());
+            assert_eq!(list.tail(), expected.last())
+        }
+    }
+
+    #[test]
+    fn sort_is_stable() {
+        let mut list: LinkedList<_> = (0..1000_u32).map(|i| ((i * 31) % 10, i)).collect();
+        list.sort_by_key(|&(key, _)| key);
+
+        let items = contents(&list);
+        assert!(items.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(list.tail(), Some(&(9, 999)))
+    }
+
+    #[test]
+    fn sort_unsized() {
+        let mut list = LinkedList::<str>::new();
+        for word in &["pear", "fig", "apple", "kiwi"] {
+            list.push_tail_boxed((*word).into())
+        }
+        list.sort_by(|a, b| a.len().cmp(&b.len()));
+
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec!["fig", "pear", "kiwi", "apple"]);
+        assert_eq!(list.tail(), Some("apple"))
+    }
+
+    #[test]
+    fn sort_panic_keeps_elements() {
+        for limit in &[0, 1, 10, 100, 300] {
+            let mut list: LinkedList<_> = (0..100_u32).map(|i| (i * 37) % 100).collect();
+            let mut calls = 0;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                list.sort_by(|a, b| {
+                    calls += 1;
+                    assert!(calls <= *limit, "comparator panicked");
+                    a.cmp(b)
+                })
+            }));
+            assert!(result.is_err());
+
+            let mut items = contents(&list);
+            assert_eq!((list.len(), list.tail()), (100, items.last()));
+            items.sort_unstable();
+            assert!(items.into_iter().eq(0..100));
+            list.push_tail(100);
+            assert_eq!(list.tail(), Some(&100))
+        }
+    }
+
+    #[test]
+    fn sorted_insert_and_removal() {
+        let mut list = LinkedList::new();
+        for &(key, id) in &[(3, 0), (1, 1), (3, 2), (2, 3), (0, 4), (3, 5)] {
+            list.insert_sorted_by((key, id), |a: &(i32, i32), b| a.0.cmp(&b.0))
+        }
+        assert_eq!(contents(&list), vec![(0, 4), (1, 1), (2, 3), (3, 0), (3, 2), (3, 5)]);
+        assert_eq!(list.tail(), Some(&(3, 5)));
+
+        assert_eq!(list.remove_first_by(|&(key, _)| key == 3), Some((3, 0)));
+        assert_eq!(list.remove_first_by(|&(_, id)| id == 5), Some((3, 5)));
+        assert_eq!(list.remove_first_by(|&(key, _)| key > 5), None);
+        assert_eq!((list.len(), list.tail()), (4, Some(&(3, 2))));
+
+        let mut words = LinkedList::<str>::new();
+        words.insert_sorted_by_boxed("b".into(), Ord::cmp);
+        words.insert_sorted_by_boxed("a".into(), Ord::cmp);
+        words.insert_sorted_by_boxed("c".into(), Ord::cmp);
+        assert_eq!(words.remove_first_by_boxed(|w| w == "b").as_deref(), Some("b"));
+        assert_eq!(words.iter().collect::<Vec<_>>(), vec!["a", "c"])
+    }
+}
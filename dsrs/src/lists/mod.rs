@@ -1,3 +1,17 @@
 pub mod linked_list;
+pub mod doubly_linked_list;
+pub mod circular_list;
+pub mod concurrent_stack;
+pub mod persistent_list;
+pub mod unrolled_list;
+pub mod skip_list;
+pub mod intrusive_list;
 
 pub use linked_list::LinkedList;
+pub use doubly_linked_list::DoublyLinkedList;
+pub use circular_list::CircularList;
+pub use concurrent_stack::ConcurrentStack;
+pub use persistent_list::PersistentList;
+pub use unrolled_list::UnrolledList;
+pub use skip_list::SkipList;
+pub use intrusive_list::IntrusiveList;
@@ -0,0 +1,280 @@
+use mem::alloc::ReprC;
+use mem::ReprC;
+
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+
+#[cfg(feature = "arc")]
+use std::sync::Arc as Shared;
+#[cfg(not(feature = "arc"))]
+use std::rc::Rc as Shared;
+
+type Link<T> = Option<Shared<Node<T>>>;
+
+#[repr(C)]
+#[derive(ReprC)]
+struct Node<T: ?Sized> {
+    next: Link<T>,
+    data: T
+}
+
+/// Immutable singly linked list, whose nodes are shared between lists.
+///
+/// Every operation returns a new list, so older versions stay valid, which
+/// is useful for backtracking. The nodes are reference counted with `Rc`,
+/// or `Arc` with the `arc` feature.
+pub struct PersistentList<T: ?Sized> {
+    head: Link<T>,
+    length: usize
+}
+
+impl<T: ?Sized> PersistentList<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { head: None, length: 0 }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// New list with `data` in front of this one, in *O*(1).
+    ///
+    /// The node is allocated twice: `data` is first moved into a boxed
+    /// node, which is then copied into the reference counted block, as the
+    /// layout of that block can't be built directly for an unsized value.
+    /// For sized values, [`cons`](PersistentList::cons) only allocates once.
+    #[must_use]
+    #[inline]
+    pub fn cons_boxed(&self, data: Box<T>) -> Self {
+        let node = ReprC::expand(data, (self.head.clone(),));
+        Self { head: Some(Shared::from(node)), length: self.length + 1 }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.data)
+    }
+
+    /// The list without its first element, sharing all of its nodes.
+    ///
+    /// Returns `None` for the empty list.
+    #[must_use]
+    #[inline]
+    pub fn tail(&self) -> Option<Self> {
+        self.head.as_ref().map(|node| Self { head: node.next.clone(), length: self.length - 1 })
+    }
+
+    /// Whether both lists start at the same node, so that they are equal
+    /// without comparing elements.
+    #[must_use]
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.head, &other.head) {
+            (Some(this), Some(other)) => Shared::ptr_eq(this, other),
+            (None, None) => true,
+            (_, _) => false
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref(), length: self.length }
+    }
+}
+
+impl<T> PersistentList<T> {
+    /// New list with `data` in front of this one, in *O*(1).
+    #[must_use]
+    #[inline]
+    pub fn cons(&self, data: T) -> Self {
+        let node = Node { next: self.head.clone(), data };
+        Self { head: Some(Shared::new(node)), length: self.length + 1 }
+    }
+}
+
+impl<T: ?Sized> Drop for PersistentList<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // only the nodes not shared with other lists are dropped
+        // here, one at a time, stopping at the first shared one
+        let mut next = self.head.take();
+        while let Some(mut node) = next {
+            next = match Shared::get_mut(&mut node) {
+                Some(node) => node.next.take(),
+                None => break
+            }
+        }
+    }
+}
+
+impl<T: ?Sized> Clone for PersistentList<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { head: self.head.clone(), length: self.length }
+    }
+}
+
+impl<T: ?Sized> Default for PersistentList<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for PersistentList<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for PersistentList<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && (self.ptr_eq(other) || self.iter().eq(other.iter()))
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for PersistentList<T> {}
+
+impl<T: ?Sized + Hash> Hash for PersistentList<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for item in self {
+            item.hash(state)
+        }
+    }
+}
+
+/// Borrowing iterator over the elements of a [`PersistentList`].
+///
+/// Created by [`PersistentList::iter`].
+pub struct Iter<'a, T: ?Sized> {
+    next: Option<&'a Node<T>>,
+    length: usize
+}
+
+impl<'a, T: ?Sized> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            self.length -= 1;
+            &node.data
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for Iter<'_, T> {}
+
+impl<T: ?Sized> Clone for Iter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { next: self.next, length: self.length }
+    }
+}
+
+impl<'a, T: ?Sized> IntoIterator for &'a PersistentList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cons_shares_structure() {
+        let empty = PersistentList::new();
+        let one = empty.cons(1);
+        let two = one.cons(2);
+        let other = one.cons(20);
+
+        assert_eq!((empty.len(), one.len(), two.len()), (0, 1, 2));
+        assert_eq!(two.iter().copied().collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), vec![20, 1]);
+        assert_eq!((two.head(), empty.head()), (Some(&2), None));
+
+        let rest = two.tail().unwrap();
+        assert!(rest.ptr_eq(&one) && other.tail().unwrap().ptr_eq(&one));
+        assert_eq!(rest, one);
+        assert_ne!(two, other);
+        assert!(empty.tail().is_none());
+
+        drop(one);
+        assert_eq!(format!("{:?}", rest), "[1]")
+    }
+
+    #[test]
+    fn unsized_nodes() {
+        let list = PersistentList::<str>::new().cons_boxed("world".into());
+        let hello = list.cons_boxed("hello".into());
+        let bye = list.cons_boxed("bye".into());
+
+        assert_eq!(hello.iter().collect::<Vec<_>>(), vec!["hello", "world"]);
+        assert_eq!(bye.head(), Some("bye"));
+        assert!(hello.tail().unwrap().ptr_eq(&bye.tail().unwrap()))
+    }
+
+    #[test]
+    fn backtracking_paths() {
+        // every path from 0 to 4 in a small DAG
+        let edges: &[&[usize]] = &[&[1, 2], &[3], &[3, 4], &[4], &[]];
+        let mut pending = vec![PersistentList::new().cons(0)];
+        let mut paths = Vec::new();
+
+        while let Some(path) = pending.pop() {
+            let node = *path.head().unwrap();
+            if node == 4 {
+                let mut found: Vec<_> = path.iter().copied().collect();
+                found.reverse();
+                paths.push(found)
+            }
+            for &next in edges[node] {
+                pending.push(path.cons(next))
+            }
+        }
+        paths.sort();
+        assert_eq!(paths, vec![vec![0, 1, 3, 4], vec![0, 2, 3, 4], vec![0, 2, 4]])
+    }
+
+    #[test]
+    fn drop_long_list() {
+        let mut list = PersistentList::new();
+        for i in 0..1_000_000_u32 {
+            list = list.cons(i)
+        }
+        let shared = list.tail().unwrap();
+        drop(list);
+        assert_eq!(shared.head(), Some(&999_998));
+        drop(shared)
+    }
+}
@@ -0,0 +1,674 @@
+use crate::random::XorShift;
+
+use mem::alloc::{Layout, ReprC};
+use mem::ptr::NonNull;
+use mem::ReprC;
+
+use std::alloc::{AllocRef, Global};
+use std::borrow::Borrow;
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::{FromIterator, FusedIterator};
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+use std::ptr;
+
+/// Maximum tower height, enough for about 2^32 entries.
+const MAX_HEIGHT: usize = 32;
+
+type Link<K, V> = Option<NonNull<Node<K, V>>>;
+
+#[repr(C)]
+#[derive(ReprC)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    // next node in each level, from the bottom up
+    tower: [Link<K, V>]
+}
+
+impl<K, V> Node<K, V> {
+    #[inline]
+    fn alloc(key: K, value: V, height: usize) -> NonNull<Self> {
+        let tower = vec![None; height].into_boxed_slice();
+        let node = ReprC::expand(tower, (key, value));
+        // SAFETY: pointers from boxes are never null
+        unsafe { NonNull::new_unchecked(Box::into_raw(node)) }
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must be a node created with [`Node::alloc`] and not linked
+    /// anywhere.
+    #[inline]
+    unsafe fn free(ptr: NonNull<Self>) -> (K, V) {
+        // SAFETY: guaranteed by the caller, and the key and value are moved
+        // out before the block is released, without dropping anything else
+        unsafe {
+            let node = ptr.as_ptr();
+            let entry = (ptr::read(&(*node).key), ptr::read(&(*node).value));
+            let layout = Layout::for_value(&*node);
+            Global.dealloc(ptr.cast().inner(), layout.inner());
+            entry
+        }
+    }
+}
+
+/// Ordered map, as a linked list with express lanes.
+///
+/// Each node has a tower of links with random height, where level `i`
+/// links every node with more than `i` levels. Searches start at the top
+/// level, skipping most of the nodes, for an expected *O*(log n) cost.
+/// The heights come from a seedable [`XorShift`], so the structure is
+/// reproducible.
+pub struct SkipList<K, V> {
+    head: [Link<K, V>; MAX_HEIGHT],
+    height: usize,
+    length: usize,
+    rng: XorShift,
+    marker: PhantomData<Box<Node<K, V>>>
+}
+
+// SAFETY: the list owns its nodes, just like a 'Box'
+unsafe impl<K: Send, V: Send> Send for SkipList<K, V> {}
+// SAFETY: shared references only give shared access to the entries
+unsafe impl<K: Sync, V: Sync> Sync for SkipList<K, V> {}
+
+impl<K, V> SkipList<K, V> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_seed(XorShift::DEFAULT_SEED)
+    }
+
+    /// Empty list, with tower heights drawn from a generator seeded
+    /// with `seed`.
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self {
+            head: [None; MAX_HEIGHT],
+            height: 0,
+            length: 0,
+            rng: XorShift::new(seed),
+            marker: PhantomData
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Each level above the first is kept with probability 1/2.
+    #[inline]
+    fn random_height(&mut self) -> usize {
+        let mut bits = self.rng.next_u64();
+        let mut height = 1;
+        while bits & 1 == 1 && height < MAX_HEIGHT {
+            bits >>= 1;
+            height += 1
+        }
+        height
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn first(&self) -> Option<(&K, &V)> {
+        // SAFETY: nodes are owned by the list, which is borrowed
+        self.head[0].map(|node| unsafe { Node::entry(node) })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut tower = &self.head[..];
+        let mut last = None;
+        for level in (0..self.height).rev() {
+            while let Some(next) = tower[level] {
+                // SAFETY: nodes are owned by the list, which is borrowed
+                tower = unsafe { &(*next.as_ptr()).tower };
+                last = Some(next)
+            }
+        }
+        // SAFETY: same as above
+        last.map(|node| unsafe { Node::entry(node) })
+    }
+
+    /// Removes the entry with the smallest key.
+    #[inline]
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let node = self.head[0]?;
+        // SAFETY: 'node' is the first in every level of its tower
+        unsafe {
+            for (level, &next) in (*node.as_ptr()).tower.iter().enumerate() {
+                self.head[level] = next
+            }
+        }
+        self.unlinked();
+        // SAFETY: the node was just unlinked
+        Some(unsafe { Node::free(node) })
+    }
+
+    /// Updates the height and length after a node was unlinked.
+    #[inline]
+    fn unlinked(&mut self) {
+        while self.height > 0 && self.head[self.height - 1].is_none() {
+            self.height -= 1
+        }
+        self.length -= 1
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        let mut next = self.head[0];
+        while let Some(node) = next {
+            // SAFETY: each node is visited once, and all of them are
+            // detached from the list below
+            unsafe {
+                next = (*node.as_ptr()).tower[0];
+                Node::free(node);
+            }
+        }
+        self.head = [None; MAX_HEIGHT];
+        self.height = 0;
+        self.length = 0
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { next: self.head[0], length: self.length, marker: PhantomData }
+    }
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    /// First node whose key is not less than `key`, or greater than it
+    /// when `inclusive` is false.
+    #[inline]
+    fn seek<Q: ?Sized + Ord>(&self, key: &Q, inclusive: bool) -> Link<K, V>
+    where K: Borrow<Q> {
+        let mut tower = &self.head[..];
+        for level in (0..self.height).rev() {
+            while let Some(next) = tower[level] {
+                // SAFETY: nodes are owned by the list, which is borrowed
+                let node = unsafe { &*next.as_ptr() };
+                let found =
+                    if inclusive { node.key.borrow() >= key } else { node.key.borrow() > key };
+                if found {
+                    break
+                }
+                tower = &node.tower
+            }
+        }
+        tower.first().copied().flatten()
+    }
+
+    /// The node with exactly `key`.
+    #[inline]
+    fn find<Q: ?Sized + Ord>(&self, key: &Q) -> Link<K, V>
+    where K: Borrow<Q> {
+        let node = self.seek(key, true)?;
+        // SAFETY: nodes are owned by the list, which is borrowed
+        if unsafe { (*node.as_ptr()).key.borrow() } == key { Some(node) } else { None }
+    }
+
+    /// For every level, the last link that precedes `key`.
+    ///
+    /// The links are either in the head or in a live node, and can be used
+    /// until the list is changed in some other way.
+    #[inline]
+    fn links_to<Q: ?Sized + Ord>(&mut self, key: &Q) -> [*mut Link<K, V>; MAX_HEIGHT]
+    where K: Borrow<Q> {
+        let mut links = [ptr::null_mut(); MAX_HEIGHT];
+        let mut tower: *mut [Link<K, V>] = &mut self.head[..];
+        for level in (0..MAX_HEIGHT).rev() {
+            // SAFETY: 'tower' is the head or a live node, both owned by
+            // the list, which is mutably borrowed
+            unsafe {
+                while let Some(next) = (*tower)[level] {
+                    if (*next.as_ptr()).key.borrow() >= key {
+                        break
+                    }
+                    tower = &mut (*next.as_ptr()).tower
+                }
+                links[level] = &mut (*tower)[level]
+            }
+        }
+        links
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&V>
+    where K: Borrow<Q> {
+        // SAFETY: nodes are owned by the list, which is borrowed
+        self.find(key).map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get_mut<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<&mut V>
+    where K: Borrow<Q> {
+        // SAFETY: nodes are owned by the list, which is mutably borrowed
+        self.find(key).map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn contains_key<Q: ?Sized + Ord>(&self, key: &Q) -> bool
+    where K: Borrow<Q> {
+        self.find(key).is_some()
+    }
+
+    /// Inserts `value` with `key`, returning the previous value for an
+    /// equal key, which is kept.
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        // drawn before taking the links, which point into 'self'
+        let height = self.random_height();
+        let links = self.links_to(&key);
+
+        // SAFETY: 'links' are valid, and the list is mutably borrowed
+        if let Some(next) = unsafe { *links[0] } {
+            let node = unsafe { &mut *next.as_ptr() };
+            if node.key == key {
+                return Some(std::mem::replace(&mut node.value, value))
+            }
+        }
+
+        let node = Node::alloc(key, value, height);
+        for (level, &link) in links.iter().enumerate().take(height) {
+            // SAFETY: 'node' is not linked yet, and 'link' is valid
+            unsafe {
+                (*node.as_ptr()).tower[level] = *link;
+                *link = Some(node)
+            }
+        }
+        self.height = self.height.max(height);
+        self.length += 1;
+        None
+    }
+
+    #[inline]
+    pub fn remove<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<V>
+    where K: Borrow<Q> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    #[inline]
+    pub fn remove_entry<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<(K, V)>
+    where K: Borrow<Q> {
+        let links = self.links_to(key);
+        // SAFETY: 'links' are valid, and the list is mutably borrowed
+        let node = unsafe { *links[0] }?;
+        // SAFETY: 'node' is live
+        let tower = unsafe {
+            if (*node.as_ptr()).key.borrow() != key {
+                return None
+            }
+            &(*node.as_ptr()).tower
+        };
+
+        for (&link, &next) in links.iter().zip(tower.iter()) {
+            // SAFETY: keys are unique, so every link before 'key' points
+            // to 'node' in the levels of its tower
+            unsafe { *link = next }
+        }
+        self.unlinked();
+        // SAFETY: the node was just unlinked
+        Some(unsafe { Node::free(node) })
+    }
+
+    /// Iterator over the entries with keys in `range`, in order.
+    ///
+    /// Ranges that start after their end are just empty.
+    #[must_use]
+    #[inline]
+    pub fn range<Q: ?Sized + Ord, R: RangeBounds<Q>>(&self, range: R) -> Range<'_, K, V>
+    where K: Borrow<Q> {
+        let next = match range.start_bound() {
+            Bound::Included(key) => self.seek(key, true),
+            Bound::Excluded(key) => self.seek(key, false),
+            Bound::Unbounded => self.head[0]
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.seek(key, false),
+            Bound::Excluded(key) => self.seek(key, true),
+            Bound::Unbounded => None
+        };
+
+        // SAFETY: nodes are owned by the list, which is borrowed
+        let inverted = match (next, end) {
+            (Some(next), Some(end)) => unsafe { (*end.as_ptr()).key < (*next.as_ptr()).key },
+            (_, _) => false
+        };
+        Range { next: if inverted { None } else { next }, end, marker: PhantomData }
+    }
+}
+
+impl<K, V> Node<K, V> {
+    /// # Safety
+    ///
+    /// `ptr` must be a live node, not mutably borrowed during `'a`.
+    #[inline]
+    unsafe fn entry<'a>(ptr: NonNull<Self>) -> (&'a K, &'a V) {
+        // SAFETY: guaranteed by the caller
+        let node = unsafe { &*ptr.as_ptr() };
+        (&node.key, &node.value)
+    }
+}
+
+impl<K, V> Drop for SkipList<K, V> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<K, V> Default for SkipList<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Clone for SkipList<K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut list = Self { rng: self.rng.clone(), ..Self::new() };
+        list.extend(self.iter().map(|(key, value)| (key.clone(), value.clone())));
+        list
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for SkipList<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for SkipList<K, V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for SkipList<K, V> {}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipList<K, V> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SkipList<K, V> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// Borrowing iterator over the entries of a [`SkipList`], in key order.
+///
+/// Created by [`SkipList::iter`].
+pub struct Iter<'a, K, V> {
+    next: Link<K, V>,
+    length: usize,
+    marker: PhantomData<(&'a K, &'a V)>
+}
+
+// SAFETY: behaves like a '(&K, &V)'
+unsafe impl<K: Sync, V: Sync> Send for Iter<'_, K, V> {}
+// SAFETY: behaves like a '(&K, &V)'
+unsafe impl<K: Sync, V: Sync> Sync for Iter<'_, K, V> {}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.next.map(|node| {
+            // SAFETY: the list is borrowed for 'a
+            unsafe {
+                self.next = (*node.as_ptr()).tower[0];
+                self.length -= 1;
+                Node::entry(node)
+            }
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { next: self.next, length: self.length, marker: PhantomData }
+    }
+}
+
+/// Borrowing iterator over a range of entries of a [`SkipList`].
+///
+/// Created by [`SkipList::range`].
+pub struct Range<'a, K, V> {
+    next: Link<K, V>,
+    // first node after the range
+    end: Link<K, V>,
+    marker: PhantomData<(&'a K, &'a V)>
+}
+
+// SAFETY: behaves like a '(&K, &V)'
+unsafe impl<K: Sync, V: Sync> Send for Range<'_, K, V> {}
+// SAFETY: behaves like a '(&K, &V)'
+unsafe impl<K: Sync, V: Sync> Sync for Range<'_, K, V> {}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let node = self.next.filter(|&node| Some(node) != self.end)?;
+        // SAFETY: the list is borrowed for 'a
+        unsafe {
+            self.next = (*node.as_ptr()).tower[0];
+            Some(Node::entry(node))
+        }
+    }
+}
+
+impl<K, V> FusedIterator for Range<'_, K, V> {}
+
+impl<K, V> Clone for Range<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { next: self.next, end: self.end, marker: PhantomData }
+    }
+}
+
+/// Owning iterator over the entries of a [`SkipList`], in key order.
+///
+/// Created by [`SkipList::into_iter`](IntoIterator::into_iter).
+pub struct IntoIter<K, V> {
+    list: SkipList<K, V>
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<(K, V)> {
+        self.list.pop_first()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+impl<K, V> IntoIterator for SkipList<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipList<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::rc::Rc;
+
+    fn keys<'a>(iter: impl Iterator<Item = (&'a u32, &'a char)>) -> Vec<u32> {
+        iter.map(|(&key, _)| key).collect()
+    }
+
+    #[test]
+    fn insert_get_and_remove() {
+        let mut list = SkipList::new();
+        assert_eq!((list.first(), list.last()), (None, None));
+        for (i, key) in [5, 1, 9, 3, 7].iter().enumerate() {
+            assert_eq!(list.insert(*key, i), None)
+        }
+        assert_eq!(list.insert(3, 10), Some(3));
+        assert_eq!(list.len(), 5);
+
+        assert_eq!((list.get(&3), list.get(&4)), (Some(&10), None));
+        *list.get_mut(&9).unwrap() += 1;
+        assert_eq!(list.first(), Some((&1, &1)));
+        assert_eq!(list.last(), Some((&9, &3)));
+
+        assert_eq!((list.remove(&1), list.remove(&1)), (Some(1), None));
+        assert_eq!(list.remove_entry(&9), Some((9, 3)));
+        assert!(!list.contains_key(&9) && list.contains_key(&5));
+        assert_eq!(format!("{:?}", list), "{3: 10, 5: 0, 7: 4}");
+
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![(3, 10), (5, 0), (7, 4)])
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let mut list = SkipList::new();
+        list.insert(String::from("b"), 2);
+        list.insert(String::from("a"), 1);
+        assert_eq!(list.get("a"), Some(&1));
+        assert_eq!(list.range::<str, _>((Bound::Excluded("a"), Bound::Unbounded)).count(), 1);
+        assert_eq!(list.remove("b"), Some(2))
+    }
+
+    #[test]
+    fn range_bounds() {
+        let list: SkipList<_, _> = (0..20).step_by(2).map(|key| (key, 'x')).collect();
+
+        assert_eq!(keys(list.range(4..10)), vec![4, 6, 8]);
+        assert_eq!(keys(list.range(3..=10)), vec![4, 6, 8, 10]);
+        assert_eq!(keys(list.range(..5)), vec![0, 2, 4]);
+        assert_eq!(keys(list.range(15..)), vec![16, 18]);
+        assert_eq!(keys(list.range((Bound::Excluded(4), Bound::Included(8)))), vec![6, 8]);
+        assert_eq!(list.range(..).count(), 10);
+
+        assert_eq!(list.range(30..).next(), None);
+        assert_eq!(list.range(5..5).next(), None);
+        assert_eq!(list.range((Bound::Included(7), Bound::Included(5))).next(), None);
+        assert_eq!(list.range((Bound::Included(9), Bound::Excluded(3))).next(), None)
+    }
+
+    #[test]
+    fn same_seed_same_towers() {
+        let towers = |seed| {
+            let mut list = SkipList::with_seed(seed);
+            list.extend((0..100).map(|key| (key, ())));
+            let mut heights = Vec::new();
+            let mut next = list.head[0];
+            while let Some(node) = next {
+                // SAFETY: the list is still alive
+                let tower = unsafe { &(*node.as_ptr()).tower };
+                heights.push(tower.len());
+                next = tower[0]
+            }
+            heights
+        };
+
+        assert_eq!(towers(1), towers(1));
+        assert_ne!(towers(1), towers(2));
+        assert!(towers(1).iter().any(|&height| height > 1))
+    }
+
+    #[test]
+    fn random_operations_against_model() {
+        let mut rng = XorShift::new(2020);
+        let mut list = SkipList::with_seed(99);
+        let mut model = BTreeMap::new();
+
+        for _ in 0..5000 {
+            let key = rng.below(300);
+            match rng.below(4) {
+                0 | 1 => assert_eq!(list.insert(key, key * 2), model.insert(key, key * 2)),
+                2 => assert_eq!(list.remove(&key), model.remove(&key)),
+                _ => {
+                    let end = key + rng.below(50);
+                    assert!(list.range(key..end).eq(model.range(key..end)))
+                },
+            }
+            assert_eq!(list.len(), model.len());
+            assert_eq!(list.last(), model.iter().next_back())
+        }
+        assert!(list.iter().eq(model.iter()));
+        assert_eq!(list.clone(), list)
+    }
+
+    #[test]
+    fn drops_every_entry() {
+        let value = Rc::new(());
+        let mut list = SkipList::new();
+        for key in 0..100 {
+            list.insert(key, Rc::clone(&value));
+        }
+        list.insert(10, Rc::clone(&value));
+        drop(list.remove(&20));
+        assert_eq!(Rc::strong_count(&value), 100);
+
+        let mut iter = list.into_iter();
+        drop(iter.next());
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 1)
+    }
+}
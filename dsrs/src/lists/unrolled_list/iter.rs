@@ -0,0 +1,292 @@
+use super::list::UnrolledList;
+use super::node::Link;
+
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// Position of an iterator end, as a node and an offset inside it.
+struct Position<T, const N: usize> {
+    node: Link<T, N>,
+    offset: usize
+}
+
+impl<T, const N: usize> Clone for Position<T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { node: self.node, offset: self.offset }
+    }
+}
+
+/// Front and back positions shared by the borrowing iterators.
+struct Range<T, const N: usize> {
+    front: Position<T, N>,
+    // one past the last item
+    back: Position<T, N>,
+    length: usize
+}
+
+impl<T, const N: usize> Range<T, N> {
+    #[inline]
+    fn new(list: &UnrolledList<T, N>) -> Self {
+        // SAFETY: 'tail' is owned by the list
+        let back_offset = list.tail.map_or(0, |tail| unsafe { tail.count() });
+        Self {
+            front: Position { node: list.head, offset: 0 },
+            back: Position { node: list.tail, offset: back_offset },
+            length: list.len()
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The list must be borrowed for as long as the range is used.
+    #[inline]
+    unsafe fn next(&mut self) -> Option<*mut T> {
+        if self.length == 0 {
+            return None
+        }
+        let mut node = self.front.node?;
+        // SAFETY: the list is borrowed, and 'length' keeps the position
+        // before the back one, so the node is live and not empty
+        unsafe {
+            if self.front.offset == node.count() {
+                node = (*node.next())?;
+                self.front = Position { node: Some(node), offset: 0 }
+            }
+        }
+        self.front.offset += 1;
+        self.length -= 1;
+        Some(node.slot(self.front.offset - 1))
+    }
+
+    /// # Safety
+    ///
+    /// The list must be borrowed for as long as the range is used.
+    #[inline]
+    unsafe fn next_back(&mut self) -> Option<*mut T> {
+        if self.length == 0 {
+            return None
+        }
+        let mut node = self.back.node?;
+        if self.back.offset == 0 {
+            // SAFETY: the list is borrowed, and 'length' keeps the position
+            // after the front one, so the node is live and not empty
+            node = unsafe { (*node.prev())? };
+            self.back = Position { node: Some(node), offset: unsafe { node.count() } }
+        }
+        self.back.offset -= 1;
+        self.length -= 1;
+        Some(node.slot(self.back.offset))
+    }
+}
+
+impl<T, const N: usize> Clone for Range<T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { front: self.front.clone(), back: self.back.clone(), length: self.length }
+    }
+}
+
+/// Borrowing iterator over the elements of an [`UnrolledList`].
+///
+/// Created by [`UnrolledList::iter`].
+pub struct Iter<'a, T, const N: usize> {
+    range: Range<T, N>,
+    marker: PhantomData<&'a T>
+}
+
+// SAFETY: behaves like a '&T'
+unsafe impl<T: Sync, const N: usize> Send for Iter<'_, T, N> {}
+// SAFETY: behaves like a '&T'
+unsafe impl<T: Sync, const N: usize> Sync for Iter<'_, T, N> {}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a
+        unsafe { self.range.next().map(|item| &*item) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.range.length, Some(self.range.length))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for Iter<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        // SAFETY: the list is borrowed for 'a
+        unsafe { self.range.next_back().map(|item| &*item) }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Iter<'_, T, N> {}
+
+impl<T, const N: usize> FusedIterator for Iter<'_, T, N> {}
+
+impl<T, const N: usize> Clone for Iter<'_, T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { range: self.range.clone(), marker: PhantomData }
+    }
+}
+
+/// Mutable borrowing iterator over the elements of an [`UnrolledList`].
+///
+/// Created by [`UnrolledList::iter_mut`].
+pub struct IterMut<'a, T, const N: usize> {
+    range: Range<T, N>,
+    marker: PhantomData<&'a mut T>
+}
+
+// SAFETY: behaves like a '&mut T'
+unsafe impl<T: Send, const N: usize> Send for IterMut<'_, T, N> {}
+// SAFETY: behaves like a '&mut T'
+unsafe impl<T: Sync, const N: usize> Sync for IterMut<'_, T, N> {}
+
+impl<'a, T, const N: usize> Iterator for IterMut<'a, T, N> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        // SAFETY: the list is mutably borrowed for 'a, and each
+        // item is yielded only once
+        unsafe { self.range.next().map(|item| &mut *item) }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.range.length, Some(self.range.length))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for IterMut<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut T> {
+        // SAFETY: the list is mutably borrowed for 'a, and each
+        // item is yielded only once
+        unsafe { self.range.next_back().map(|item| &mut *item) }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IterMut<'_, T, N> {}
+
+impl<T, const N: usize> FusedIterator for IterMut<'_, T, N> {}
+
+/// Owning iterator over the elements of an [`UnrolledList`].
+///
+/// Created by [`UnrolledList::into_iter`](IntoIterator::into_iter).
+pub struct IntoIter<T, const N: usize> {
+    list: UnrolledList<T, N>
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len(), Some(self.list.len()))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter { range: Range::new(self), marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, N> {
+        IterMut { range: Range::new(self), marker: PhantomData }
+    }
+}
+
+impl<T, const N: usize> IntoIterator for UnrolledList<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T, N> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a UnrolledList<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, N>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T, N> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut UnrolledList<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T, N>;
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, T, N> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_ended() {
+        let mut list: UnrolledList<_, 3> = (0..10).collect();
+
+        let mut iter = list.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(&0), Some(&9)));
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.clone().rev().collect::<Vec<_>>(), vec![&8, &7, &6, &5, &4, &3, &2, &1]);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6, &7, &8]);
+
+        for x in &mut list {
+            *x *= 10
+        }
+        let mut iter = list.iter_mut();
+        if let (Some(first), Some(last)) = (iter.next(), iter.next_back()) {
+            std::mem::swap(first, last)
+        }
+
+        let mut iter = list.into_iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(90), Some(0)));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![10, 20, 30, 40, 50, 60, 70, 80])
+    }
+
+    #[test]
+    fn meet_in_the_middle() {
+        let list: UnrolledList<_, 2> = (0..3).collect();
+        let mut iter = list.iter();
+        assert_eq!((iter.next_back(), iter.next_back()), (Some(&2), Some(&1)));
+        assert_eq!((iter.next(), iter.next(), iter.next_back()), (Some(&0), None, None));
+
+        let empty = UnrolledList::<i32, 4>::new();
+        assert_eq!(empty.iter().next_back(), None)
+    }
+}
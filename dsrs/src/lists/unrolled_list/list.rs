@@ -0,0 +1,500 @@
+use super::node::{Link, NodePtr};
+
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+/// Doubly linked list of chunks, each one holding up to `N` items inline.
+///
+/// Storing many items per node means less pointer chasing and better
+/// cache usage than [`LinkedList`](crate::lists::LinkedList). Every node
+/// holds at least one item, and removals merge nodes that get less than
+/// half full.
+pub struct UnrolledList<T, const N: usize> {
+    pub(super) head: Link<T, N>,
+    pub(super) tail: Link<T, N>,
+    pub(super) length: usize,
+    pub(super) marker: PhantomData<Box<T>>
+}
+
+// SAFETY: the list owns its nodes and items, just like a 'Box'
+unsafe impl<T: Send, const N: usize> Send for UnrolledList<T, N> {}
+// SAFETY: shared references only give shared access to the items
+unsafe impl<T: Sync, const N: usize> Sync for UnrolledList<T, N> {}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { head: None, tail: None, length: 0, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Links `node` right after `prev`, or at the head when `prev` is
+    /// `None`.
+    ///
+    /// # Safety
+    ///
+    /// `prev` must be a node owned by this list and `node` must be
+    /// unlinked.
+    #[inline]
+    unsafe fn link_after(&mut self, prev: Link<T, N>, node: NodePtr<T, N>) {
+        // SAFETY: all nodes are live, guaranteed by the caller
+        unsafe {
+            let next = match prev {
+                Some(prev) => *prev.next(),
+                None => self.head
+            };
+            *node.prev() = prev;
+            *node.next() = next;
+
+            match prev {
+                Some(prev) => *prev.next() = Some(node),
+                None => self.head = Some(node)
+            }
+            match next {
+                Some(next) => *next.prev() = Some(node),
+                None => self.tail = Some(node)
+            }
+        }
+    }
+
+    /// Unlinks and frees an empty node.
+    ///
+    /// # Safety
+    ///
+    /// `node` must be an empty node owned by this list.
+    #[inline]
+    unsafe fn free_node(&mut self, node: NodePtr<T, N>) {
+        // SAFETY: all nodes are live, guaranteed by the caller
+        unsafe {
+            debug_assert_eq!(node.count(), 0);
+            let (prev, next) = (*node.prev(), *node.next());
+            match prev {
+                Some(prev) => *prev.next() = next,
+                None => self.head = next
+            }
+            match next {
+                Some(next) => *next.prev() = prev,
+                None => self.tail = prev
+            }
+            node.free()
+        }
+    }
+
+    /// Finds the node holding `index` and the position inside it, walking
+    /// from the closest end.
+    #[inline]
+    fn locate(&self, index: usize) -> Option<(NodePtr<T, N>, usize)> {
+        if index >= self.length {
+            return None
+        }
+
+        // SAFETY: every node is owned by the list, and their counts add up
+        // to 'length', so the walks stop before the ends
+        unsafe {
+            if index < self.length / 2 {
+                let (mut node, mut offset) = (self.head?, index);
+                while offset >= node.count() {
+                    offset -= node.count();
+                    node = (*node.next())?
+                }
+                Some((node, offset))
+            } else {
+                let (mut node, mut back) = (self.tail?, self.length - 1 - index);
+                while back >= node.count() {
+                    back -= node.count();
+                    node = (*node.prev())?
+                }
+                Some((node, node.count() - 1 - back))
+            }
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        // SAFETY: 'offset' is an initialized item of a node in the list
+        self.locate(index).map(|(node, offset)| unsafe { &*node.slot(offset) })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        // SAFETY: 'offset' is an initialized item of a node in the list,
+        // which is mutably borrowed
+        self.locate(index).map(|(node, offset)| unsafe { &mut *node.slot(offset) })
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn back(&self) -> Option<&T> {
+        self.length.checked_sub(1).and_then(|last| self.get(last))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.length.checked_sub(1).and_then(move |last| self.get_mut(last))
+    }
+
+    #[inline]
+    pub fn push_front(&mut self, value: T) {
+        // SAFETY: 'head' is owned by the list
+        let node = match self.head {
+            Some(head) if unsafe { head.count() } < N => head,
+            _ => {
+                let node = NodePtr::alloc();
+                // SAFETY: the new node is unlinked
+                unsafe { self.link_after(None, node) };
+                node
+            },
+        };
+        // SAFETY: the node is not full
+        unsafe { node.insert(0, value) };
+        self.length += 1
+    }
+
+    #[inline]
+    pub fn push_back(&mut self, value: T) {
+        // SAFETY: 'tail' is owned by the list
+        let node = match self.tail {
+            Some(tail) if unsafe { tail.count() } < N => tail,
+            _ => {
+                let node = NodePtr::alloc();
+                // SAFETY: the new node is unlinked, and 'tail' is
+                // owned by the list
+                unsafe { self.link_after(self.tail, node) };
+                node
+            },
+        };
+        // SAFETY: the node is not full
+        unsafe { node.insert(node.count(), value) };
+        self.length += 1
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head?;
+        // SAFETY: nodes in the list are never empty
+        let (value, empty) = unsafe { (head.remove(0), head.count() == 0) };
+        if empty {
+            // SAFETY: 'head' is owned by the list and now empty
+            unsafe { self.free_node(head) }
+        }
+        self.length -= 1;
+        Some(value)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail?;
+        // SAFETY: nodes in the list are never empty
+        let (value, empty) = unsafe { (tail.remove(tail.count() - 1), tail.count() == 0) };
+        if empty {
+            // SAFETY: 'tail' is owned by the list and now empty
+            unsafe { self.free_node(tail) }
+        }
+        self.length -= 1;
+        Some(value)
+    }
+
+    /// Inserts `value` at `index`, shifting every element after it.
+    ///
+    /// A full node is split in two halves first, so this only moves
+    /// elements inside a single node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > self.len()`.
+    #[inline]
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.length, "insertion index out of bounds");
+        let (node, offset) = match self.locate(index) {
+            Some(found) => found,
+            None => return self.push_back(value)
+        };
+
+        // SAFETY: 'node' is owned by the list, and the new node is
+        // linked right after it
+        let (node, offset) = unsafe {
+            if node.count() < N {
+                (node, offset)
+            } else {
+                let keep = N / 2;
+                let new = NodePtr::alloc();
+                node.move_items(keep, new);
+                self.link_after(Some(node), new);
+
+                if offset <= keep {
+                    (node, offset)
+                } else {
+                    (new, offset - keep)
+                }
+            }
+        };
+        // SAFETY: the node is not full and 'offset' is at most its count
+        unsafe { node.insert(offset, value) };
+        self.length += 1
+    }
+
+    /// Removes and returns the element at `index`, or `None` if it is out
+    /// of bounds.
+    ///
+    /// When a node gets less than half full, the next node is merged into
+    /// it, if both fit in a single node.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let (node, offset) = self.locate(index)?;
+        // SAFETY: 'node' and 'next' are owned by the list and 'offset' is
+        // an initialized item
+        let value = unsafe {
+            let value = node.remove(offset);
+            let count = node.count();
+
+            if count == 0 {
+                self.free_node(node)
+            } else if count < N / 2 {
+                if let Some(next) = *node.next() {
+                    if count + next.count() <= N {
+                        next.move_items(0, node);
+                        self.free_node(next)
+                    }
+                }
+            }
+            value
+        };
+        self.length -= 1;
+        Some(value)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.tail = None;
+        self.length = 0;
+
+        let mut next = self.head.take();
+        while let Some(node) = next {
+            // SAFETY: every node is owned by the list and is only
+            // visited once
+            unsafe {
+                next = *node.next();
+                node.clear();
+                node.free()
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for UnrolledList<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<T, const N: usize> Default for UnrolledList<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for UnrolledList<T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for UnrolledList<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for UnrolledList<T, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for UnrolledList<T, N> {}
+
+impl<T, const N: usize> FromIterator<T> for UnrolledList<T, N> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, const N: usize> Extend<T> for UnrolledList<T, N> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::XorShift;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    fn contents<const N: usize>(list: &UnrolledList<i32, N>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    fn node_counts<T, const N: usize>(list: &UnrolledList<T, N>) -> Vec<usize> {
+        let mut counts = Vec::new();
+        let mut next = list.head;
+        while let Some(node) = next {
+            // SAFETY: every node is owned by the list
+            unsafe {
+                counts.push(node.count());
+                next = *node.next()
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn push_and_pop_both_ends() {
+        let mut list = UnrolledList::<_, 3>::new();
+        for i in 0..5 {
+            list.push_back(i);
+            list.push_front(-i - 1)
+        }
+        assert_eq!(contents(&list), vec![-5, -4, -3, -2, -1, 0, 1, 2, 3, 4]);
+        assert_eq!(node_counts(&list), vec![1, 3, 3, 3]);
+        assert_eq!((list.front(), list.back(), list.len()), (Some(&-5), Some(&4), 10));
+
+        for i in 0..5 {
+            assert_eq!(list.pop_back(), Some(4 - i));
+            assert_eq!(list.pop_front(), Some(-5 + i))
+        }
+        assert_eq!((list.pop_front(), list.pop_back(), list.front()), (None, None, None));
+        assert!(list.is_empty() && list.tail.is_none())
+    }
+
+    #[test]
+    fn split_and_merge_nodes() {
+        let mut list: UnrolledList<_, 4> = (0..8).collect();
+        assert_eq!(node_counts(&list), vec![4, 4]);
+
+        list.insert(1, 10);
+        assert_eq!(node_counts(&list), vec![3, 2, 4]);
+        list.insert(7, 11);
+        list.insert(10, 12);
+        assert_eq!(contents(&list), vec![0, 10, 1, 2, 3, 4, 5, 11, 6, 7, 12]);
+        assert_eq!(node_counts(&list), vec![3, 2, 3, 3]);
+        assert_eq!((list.get(7), list.get(11)), (Some(&11), None));
+
+        assert_eq!(list.remove(3), Some(2));
+        assert_eq!(list.remove(3), Some(3));
+        assert_eq!(list.remove(3), Some(4));
+        assert_eq!(node_counts(&list), vec![3, 2, 3]);
+        assert_eq!(list.remove(20), None);
+        if let Some(x) = list.get_mut(5) {
+            *x *= 10
+        }
+        assert_eq!(contents(&list), vec![0, 10, 1, 5, 11, 60, 7, 12])
+    }
+
+    #[test]
+    fn single_item_nodes() {
+        let mut list = UnrolledList::<_, 1>::new();
+        list.insert(0, 2);
+        list.insert(0, 0);
+        list.insert(1, 1);
+        list.insert(3, 3);
+        assert_eq!(contents(&list), vec![0, 1, 2, 3]);
+        assert_eq!(node_counts(&list), vec![1, 1, 1, 1]);
+        assert_eq!((list.remove(1), list.remove(1)), (Some(1), Some(2)));
+        assert_eq!(contents(&list), vec![0, 3])
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn insert_out_of_bounds() {
+        let mut list = UnrolledList::<i32, 2>::new();
+        list.insert(1, 0)
+    }
+
+    #[test]
+    fn random_operations_against_model() {
+        let mut list = UnrolledList::<_, 5>::new();
+        let mut model = VecDeque::new();
+
+        let mut rng = XorShift::new(0x2545_f491);
+        for value in 0..5000 {
+            let index = rng.below(model.len() + 1);
+            match rng.below(6) {
+                0 => {
+                    list.push_front(value);
+                    model.push_front(value)
+                },
+                1 => assert_eq!(list.pop_back(), model.pop_back()),
+                2 | 3 => {
+                    list.insert(index, value);
+                    model.insert(index, value)
+                },
+                _ => assert_eq!(list.remove(index), model.remove(index)),
+            }
+            assert_eq!(list.len(), model.len());
+            assert_eq!(node_counts(&list).iter().sum::<usize>(), model.len());
+            assert!(node_counts(&list).iter().all(|&count| count > 0))
+        }
+        assert!(list.iter().eq(model.iter()))
+    }
+
+    #[test]
+    fn drops_every_item() {
+        let value = Rc::new(());
+        let mut list = UnrolledList::<_, 8>::new();
+        for i in 0..100 {
+            list.insert(i / 2, Rc::clone(&value))
+        }
+        let _ = list.remove(50);
+        let copy = list.clone();
+        assert_eq!(Rc::strong_count(&value), 199);
+        drop((list, copy));
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let mut units = UnrolledList::<(), 4>::new();
+        units.extend(vec![(); 10]);
+        assert_eq!((units.len(), units.pop_back()), (10, Some(())))
+    }
+}
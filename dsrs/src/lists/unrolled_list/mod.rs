@@ -0,0 +1,6 @@
+mod node;
+mod list;
+mod iter;
+
+pub use list::UnrolledList;
+pub use iter::{IntoIter, Iter, IterMut};
@@ -0,0 +1,200 @@
+use mem::alloc::Layout;
+use mem::ptr::NonNull;
+
+use std::alloc::{handle_alloc_error, AllocRef, Global};
+use std::marker::PhantomData;
+use std::ptr;
+
+pub(super) type Link<T, const N: usize> = Option<NodePtr<T, N>>;
+
+/// Pointer to a node with room for `N` items.
+///
+/// There is no struct for the node, its layout is computed field by field
+/// with [`Layout::extend_many`]: links to the `prev` and `next` nodes, the
+/// item count and then the `[T; N]` array, of which only the first
+/// `count` items are initialized.
+pub(super) struct NodePtr<T, const N: usize>(NonNull<u8>, PhantomData<*mut T>);
+
+impl<T, const N: usize> Clone for NodePtr<T, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const N: usize> Copy for NodePtr<T, N> {}
+
+impl<T, const N: usize> PartialEq for NodePtr<T, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T, const N: usize> NodePtr<T, N> {
+    /// Node layout and the offsets of `prev`, `next`, `count` and `items`.
+    const LAYOUT: (Layout, [usize; 4]) = match Layout::EMPTY.extend_many([
+        Layout::new::<Link<T, N>>(),
+        Layout::new::<Link<T, N>>(),
+        Layout::new::<usize>(),
+        Layout::new::<[T; N]>(),
+    ]) {
+        Ok((layout, offsets)) => (layout.pad_to_align(), offsets),
+        Err(_) => panic!("unrolled list node is too big")
+    };
+
+    /// Allocates an empty and unlinked node.
+    #[inline]
+    pub fn alloc() -> Self {
+        assert!(N > 0, "unrolled list nodes must hold at least one item");
+
+        let layout = Self::LAYOUT.0;
+        let node = match Global.alloc(layout.inner()) {
+            Err(_) => handle_alloc_error(layout.inner()),
+            Ok(ptr) => Self(NonNull::from_inner(ptr.cast()), PhantomData)
+        };
+        // SAFETY: the fields were just allocated
+        unsafe {
+            node.prev().write(None);
+            node.next().write(None);
+            node.field::<usize>(2).write(0)
+        }
+        node
+    }
+
+    /// # Safety
+    ///
+    /// `self` must be unlinked and its items dropped or moved out.
+    #[inline]
+    pub unsafe fn free(self) {
+        // SAFETY: guaranteed by the caller
+        unsafe { Global.dealloc(self.0.inner(), Self::LAYOUT.0.inner()) }
+    }
+
+    #[inline]
+    fn field<F>(self, index: usize) -> *mut F {
+        // SAFETY: every offset is inside the node allocation
+        unsafe { self.0.as_ptr().add(Self::LAYOUT.1[index]).cast() }
+    }
+
+    #[inline]
+    pub fn prev(self) -> *mut Link<T, N> {
+        self.field(0)
+    }
+
+    #[inline]
+    pub fn next(self) -> *mut Link<T, N> {
+        self.field(1)
+    }
+
+    /// # Safety
+    ///
+    /// `self` must be a live node.
+    #[inline]
+    pub unsafe fn count(self) -> usize {
+        // SAFETY: guaranteed by the caller
+        unsafe { *self.field::<usize>(2) }
+    }
+
+    /// # Safety
+    ///
+    /// `self` must be a live node, and the first `count` items must be
+    /// initialized.
+    #[inline]
+    unsafe fn set_count(self, count: usize) {
+        debug_assert!(count <= N);
+        // SAFETY: guaranteed by the caller
+        unsafe { *self.field::<usize>(2) = count }
+    }
+
+    /// Pointer to the item at `index`, which might be uninitialized.
+    #[inline]
+    pub fn slot(self, index: usize) -> *mut T {
+        debug_assert!(index <= N);
+        // SAFETY: 'index' is inside the items array, or right after it
+        unsafe { self.field::<T>(3).add(index) }
+    }
+
+    /// # Safety
+    ///
+    /// `self` must be a live node, not borrowed at all during `'a`.
+    #[inline]
+    pub unsafe fn items_mut<'a>(self) -> &'a mut [T] {
+        // SAFETY: guaranteed by the caller
+        unsafe { std::slice::from_raw_parts_mut(self.slot(0), self.count()) }
+    }
+
+    /// Inserts `value` at `index`, shifting the next items.
+    ///
+    /// # Safety
+    ///
+    /// `self` must be a live node, that is not full, and `index` can't be
+    /// greater than its count.
+    #[inline]
+    pub unsafe fn insert(self, index: usize, value: T) {
+        // SAFETY: guaranteed by the caller
+        unsafe {
+            let count = self.count();
+            debug_assert!(index <= count && count < N);
+
+            ptr::copy(self.slot(index), self.slot(index + 1), count - index);
+            self.slot(index).write(value);
+            self.set_count(count + 1)
+        }
+    }
+
+    /// Removes the item at `index`, shifting the next items.
+    ///
+    /// # Safety
+    ///
+    /// `self` must be a live node and `index` must be less than its count.
+    #[inline]
+    pub unsafe fn remove(self, index: usize) -> T {
+        // SAFETY: guaranteed by the caller
+        unsafe {
+            let count = self.count();
+            debug_assert!(index < count);
+
+            let value = self.slot(index).read();
+            ptr::copy(self.slot(index + 1), self.slot(index), count - index - 1);
+            self.set_count(count - 1);
+            value
+        }
+    }
+
+    /// Moves the items from `start` onwards to the end of `other`.
+    ///
+    /// # Safety
+    ///
+    /// Both must be live and distinct nodes, `start` can't be greater than
+    /// the count of `self`, and the moved items must fit in `other`.
+    #[inline]
+    pub unsafe fn move_items(self, start: usize, other: Self) {
+        // SAFETY: guaranteed by the caller
+        unsafe {
+            let (count, other_count) = (self.count(), other.count());
+            let moved = count - start;
+            debug_assert!(self != other && other_count + moved <= N);
+
+            ptr::copy_nonoverlapping(self.slot(start), other.slot(other_count), moved);
+            other.set_count(other_count + moved);
+            self.set_count(start)
+        }
+    }
+
+    /// Drops every item in the node.
+    ///
+    /// # Safety
+    ///
+    /// `self` must be a live node.
+    #[inline]
+    pub unsafe fn clear(self) {
+        // SAFETY: guaranteed by the caller, the count is reset before
+        // dropping, in case of panics
+        unsafe {
+            let items: *mut [T] = self.items_mut();
+            self.set_count(0);
+            ptr::drop_in_place(items)
+        }
+    }
+}
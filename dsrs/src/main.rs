@@ -20,8 +20,12 @@ fn main() {
     println!("{:?}", list.tail());
     println!("{:?}", list);
 
-    while let Some(elem) = list.next() {
+    for elem in &list {
+        println!("{:?}", elem)
+    }
+    println!("{:?}", list);
+
+    for elem in list {
         println!("{:?}", elem)
     }
-    println!("{:?}", list)
 }
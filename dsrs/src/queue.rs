@@ -0,0 +1,324 @@
+//! First in, first out queue.
+use crate::lists::linked_list::{IntoIter, Iter, IterMut};
+use crate::lists::LinkedList;
+
+use std::alloc::{AllocRef, Global};
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::{FromIterator, FusedIterator};
+
+/// Queue over a [`LinkedList`], with values entering at the tail and
+/// leaving at the head, both in *O*(1).
+pub struct Queue<T: ?Sized, A: AllocRef = Global> {
+    list: LinkedList<T, A>
+}
+
+impl<T: ?Sized> Queue<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T: ?Sized, A: AllocRef> Queue<T, A> {
+    /// Creates an empty queue, whose nodes will be allocated with `alloc`.
+    #[must_use]
+    #[inline]
+    pub const fn new_in(alloc: A) -> Self {
+        Self { list: LinkedList::new_in(alloc) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Next value to leave the queue.
+    #[must_use]
+    #[inline]
+    pub const fn peek(&self) -> Option<&T> {
+        self.list.head()
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn peek_mut(&mut self) -> Option<&mut T> {
+        self.list.head_mut()
+    }
+
+    #[inline]
+    pub fn enqueue_boxed(&mut self, value: Box<T>) {
+        self.list.push_tail_boxed(value)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn dequeue_boxed(&mut self) -> Option<Box<T>> {
+        self.list.pop_boxed()
+    }
+
+    /// Calls `update` on every value, from the head to the tail.
+    ///
+    /// Any extra arguments for the update can be captured by the closure.
+    #[inline]
+    pub fn update_all<F: FnMut(&mut T)>(&mut self, update: F) {
+        self.list.iter_mut().for_each(update)
+    }
+
+    /// Keeps only the values for which `keep` returns `true`, without
+    /// changing their order.
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        let mut cursor = self.list.cursor_front_mut();
+        while let Some(value) = cursor.current() {
+            if keep(value) {
+                cursor.move_next()
+            } else {
+                drop(cursor.remove_current_boxed())
+            }
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.list.clear()
+    }
+
+    /// Iterates from the head to the tail, in the order the values would
+    /// be dequeued.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.list.iter()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.list.iter_mut()
+    }
+
+    /// The underlying list, with the head of the queue at its head.
+    #[must_use]
+    #[inline]
+    pub fn into_list(self) -> LinkedList<T, A> {
+        self.list
+    }
+}
+
+impl<T, A: AllocRef> Queue<T, A> {
+    #[inline]
+    pub fn enqueue(&mut self, value: T) {
+        self.list.push_tail(value)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    /// Dequeues values while `predicate` holds for the head of the queue.
+    ///
+    /// Values are only removed as the iterator advances, so dropping it
+    /// early keeps the rest in the queue.
+    #[must_use]
+    #[inline]
+    pub fn drain_while<P: FnMut(&T) -> bool>(&mut self, predicate: P) -> DrainWhile<'_, T, A, P> {
+        DrainWhile { queue: self, predicate: Some(predicate) }
+    }
+}
+
+/// Iterator created by [`Queue::drain_while`].
+pub struct DrainWhile<'a, T, A: AllocRef, P> {
+    queue: &'a mut Queue<T, A>,
+    // dropped after the first value that fails it
+    predicate: Option<P>
+}
+
+impl<T, A: AllocRef, P: FnMut(&T) -> bool> Iterator for DrainWhile<'_, T, A, P> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let predicate = self.predicate.as_mut()?;
+        match self.queue.peek() {
+            Some(value) if predicate(value) => self.queue.dequeue(),
+            _ => {
+                self.predicate = None;
+                None
+            },
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.predicate {
+            None => (0, Some(0)),
+            Some(_) => (0, Some(self.queue.len()))
+        }
+    }
+}
+
+impl<T, A: AllocRef, P: FnMut(&T) -> bool> FusedIterator for DrainWhile<'_, T, A, P> {}
+
+impl<T: Debug, A: AllocRef, P> Debug for DrainWhile<'_, T, A, P> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("DrainWhile").field(&self.queue).finish()
+    }
+}
+
+impl<T: ?Sized, A: AllocRef> From<LinkedList<T, A>> for Queue<T, A> {
+    /// Queue with the head of `list` as its first value.
+    #[inline]
+    fn from(list: LinkedList<T, A>) -> Self {
+        Self { list }
+    }
+}
+
+impl<T: ?Sized, A: AllocRef + Default> Default for Queue<T, A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
+
+impl<T: Clone, A: AllocRef + Clone> Clone for Queue<T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { list: self.list.clone() }
+    }
+}
+
+impl<T: ?Sized + Debug, A: AllocRef> Debug for Queue<T, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ?Sized + PartialEq, A: AllocRef> PartialEq for Queue<T, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl<T: ?Sized + Eq, A: AllocRef> Eq for Queue<T, A> {}
+
+impl<T> FromIterator<T> for Queue<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self { list: LinkedList::from_iter(iter) }
+    }
+}
+
+impl<T, A: AllocRef> Extend<T> for Queue<T, A> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.list.extend(iter)
+    }
+}
+
+impl<T, A: AllocRef> IntoIterator for Queue<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    /// Iterates in dequeue order.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, T: ?Sized, A: AllocRef> IntoIterator for &'a Queue<T, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: ?Sized, A: AllocRef> IntoIterator for &'a mut Queue<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_in_first_out() {
+        let mut queue = Queue::new();
+        assert_eq!(queue.dequeue(), None::<i32>);
+
+        queue.enqueue(1);
+        queue.enqueue(2);
+        assert_eq!((queue.peek(), queue.len()), (Some(&1), 2));
+        assert_eq!(queue.dequeue(), Some(1));
+        queue.enqueue(3);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(queue.dequeue(), Some(2));
+        assert_eq!(queue.dequeue(), Some(3));
+        assert!(queue.is_empty());
+        assert_eq!(queue.peek(), None)
+    }
+
+    #[test]
+    fn update_in_place() {
+        let mut queue: Queue<_> = (1..=4).collect();
+        let step = 10;
+        queue.update_all(|value| *value *= step);
+        assert_eq!(format!("{:?}", queue), "[10, 20, 30, 40]");
+
+        queue.retain(|&value| value != 20 && value != 40);
+        assert_eq!(queue.len(), 2);
+        queue.enqueue(50);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), vec![10, 30, 50])
+    }
+
+    #[test]
+    fn drain_prefix() {
+        let mut queue: Queue<_> = vec![1, 3, 4, 5, 7].into_iter().collect();
+        let mut odd = queue.drain_while(|value| value % 2 == 1);
+        assert_eq!(odd.next(), Some(1));
+        assert_eq!(odd.next(), Some(3));
+        assert_eq!(odd.next(), None);
+        assert_eq!(odd.size_hint(), (0, Some(0)));
+        assert_eq!(queue.peek(), Some(&4));
+
+        assert_eq!(queue.drain_while(|_| true).next(), Some(4));
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![5, 7])
+    }
+
+    #[test]
+    fn unsized_values() {
+        let mut queue = Queue::<str>::new();
+        queue.enqueue_boxed("first".into());
+        queue.enqueue_boxed("second".into());
+        queue.enqueue_boxed("third".into());
+
+        queue.retain(|value| !value.starts_with('s'));
+        queue.update_all(str::make_ascii_uppercase);
+        assert_eq!(queue.peek(), Some("FIRST"));
+        assert_eq!(queue.dequeue_boxed().as_deref(), Some("FIRST"));
+        assert_eq!(queue.dequeue_boxed().as_deref(), Some("THIRD"));
+        assert_eq!(queue.dequeue_boxed(), None)
+    }
+}
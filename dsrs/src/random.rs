@@ -0,0 +1,79 @@
+//! Small pseudorandom generators for randomized structures.
+//!
+//! These are not suitable for cryptography, they only need to be fast
+//! and reproducible, so that tests with a fixed seed are deterministic.
+
+/// Marsaglia's xorshift64* generator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XorShift {
+    state: u64
+}
+
+impl XorShift {
+    /// Seed used by [`Default`].
+    pub const DEFAULT_SEED: u64 = 0x853c_49e6_748f_ea9b;
+
+    /// Creates a generator from `seed`.
+    ///
+    /// A zero state would only ever produce zeros, so that seed is
+    /// replaced by [`DEFAULT_SEED`](XorShift::DEFAULT_SEED).
+    #[must_use]
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: if seed == 0 { Self::DEFAULT_SEED } else { seed } }
+    }
+
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniformly distributed value in `0..n`, with a negligible bias.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is zero.
+    #[inline]
+    // the result is always below 'n'
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range for random value");
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+}
+
+impl Default for XorShift {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let (mut a, mut b) = (XorShift::new(42), XorShift::new(42));
+        let first: Vec<_> = (0..8).map(|_| a.next_u64()).collect();
+        assert!(first.iter().all(|&x| x == b.next_u64()));
+        let mut c = XorShift::new(43);
+        assert_ne!(first, (0..8).map(|_| c.next_u64()).collect::<Vec<_>>());
+
+        assert_eq!(XorShift::new(0), XorShift::default())
+    }
+
+    #[test]
+    fn below_covers_range() {
+        let mut rng = XorShift::new(7);
+        let mut seen = [0_usize; 6];
+        for _ in 0..6000 {
+            seen[rng.below(6)] += 1
+        }
+        assert!(seen.iter().all(|&count| count > 800 && count < 1200))
+    }
+}
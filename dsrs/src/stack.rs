@@ -0,0 +1,280 @@
+//! Last in, first out stack, optionally bounded.
+use crate::lists::LinkedList;
+
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::marker::PhantomData;
+
+/// Storage for the values of a [`Stack`].
+pub trait StackLike<T>: Default {
+    fn len(&self) -> usize;
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push_top(&mut self, value: T);
+
+    fn pop_top(&mut self) -> Option<T>;
+
+    fn top(&self) -> Option<&T>;
+
+    fn top_mut(&mut self) -> Option<&mut T>;
+}
+
+/// Top at the end of the vector.
+impl<T> StackLike<T> for Vec<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn push_top(&mut self, value: T) {
+        self.push(value)
+    }
+
+    #[inline]
+    fn pop_top(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    #[inline]
+    fn top(&self) -> Option<&T> {
+        self.last()
+    }
+
+    #[inline]
+    fn top_mut(&mut self) -> Option<&mut T> {
+        self.last_mut()
+    }
+}
+
+/// Top at the head of the list.
+impl<T> StackLike<T> for LinkedList<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn push_top(&mut self, value: T) {
+        self.push_head(value)
+    }
+
+    #[inline]
+    fn pop_top(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    #[inline]
+    fn top(&self) -> Option<&T> {
+        self.head()
+    }
+
+    #[inline]
+    fn top_mut(&mut self) -> Option<&mut T> {
+        self.head_mut()
+    }
+}
+
+/// Error from pushing to a [`Stack`] at its limit, with the value that
+/// was not pushed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Full<T>(pub T);
+
+impl<T> Debug for Full<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Full").finish()
+    }
+}
+
+impl<T> Display for Full<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "stack is full")
+    }
+}
+
+impl<T> Error for Full<T> {}
+
+/// Stack with values stored in `S`, which may hold at most `limit` of
+/// them.
+pub struct Stack<T, S: StackLike<T> = Vec<T>> {
+    storage: S,
+    limit: Option<usize>,
+    marker: PhantomData<T>
+}
+
+/// [`Stack`] over a [`Vec`].
+pub type VecStack<T> = Stack<T, Vec<T>>;
+/// [`Stack`] over a [`LinkedList`].
+pub type ListStack<T> = Stack<T, LinkedList<T>>;
+
+impl<T, S: StackLike<T>> Stack<T, S> {
+    /// Creates an empty stack without a limit.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self { storage: S::default(), limit: None, marker: PhantomData }
+    }
+
+    /// Creates an empty stack that holds at most `limit` values.
+    #[must_use]
+    #[inline]
+    pub fn bounded(limit: usize) -> Self {
+        Self { limit: Some(limit), ..Self::new() }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.limit.map_or(false, |limit| self.len() >= limit)
+    }
+
+    /// Pushes `value` to the top of the stack.
+    ///
+    /// # Errors
+    ///
+    /// If the stack is at its limit, `value` is given back in [`Full`].
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), Full<T>> {
+        if self.is_full() {
+            return Err(Full(value))
+        }
+        self.storage.push_top(value);
+        Ok(())
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.storage.pop_top()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.storage.top()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.storage.top_mut()
+    }
+
+    /// Pops every value into `dispose`, from the top to the bottom.
+    /// Returns how many values were disposed.
+    #[inline]
+    pub fn clear_with<F: FnMut(T)>(&mut self, mut dispose: F) -> usize {
+        let mut count = 0;
+        while let Some(value) = self.pop() {
+            dispose(value);
+            count += 1
+        }
+        count
+    }
+
+    #[inline]
+    pub fn clear(&mut self) -> usize {
+        self.clear_with(drop)
+    }
+
+    /// The underlying storage.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+impl<T, S: StackLike<T>> Default for Stack<T, S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S: StackLike<T> + Clone> Clone for Stack<T, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { storage: self.storage.clone(), limit: self.limit, marker: PhantomData }
+    }
+}
+
+impl<T, S: StackLike<T> + Debug> Debug for Stack<T, S> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stack").field("storage", &self.storage).field("limit", &self.limit).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_in_first_out<S: StackLike<u32>>() {
+        let mut stack = Stack::<u32, S>::new();
+        assert_eq!((stack.pop(), stack.peek()), (None, None));
+
+        for value in 0..5 {
+            assert_eq!(stack.push(value), Ok(()))
+        }
+        assert_eq!((stack.len(), stack.peek()), (5, Some(&4)));
+        assert_eq!(stack.pop(), Some(4));
+        *stack.peek_mut().unwrap() = 30;
+        assert_eq!(stack.pop(), Some(30));
+
+        let mut disposed = Vec::new();
+        assert_eq!(stack.clear_with(|value| disposed.push(value)), 3);
+        assert_eq!(disposed, vec![2, 1, 0]);
+        assert!(stack.is_empty());
+        assert_eq!(stack.clear(), 0)
+    }
+
+    #[test]
+    fn vec_backend() {
+        last_in_first_out::<Vec<_>>()
+    }
+
+    #[test]
+    fn list_backend() {
+        last_in_first_out::<LinkedList<_>>()
+    }
+
+    #[test]
+    fn bounded() {
+        let mut stack = ListStack::bounded(2);
+        assert_eq!(stack.limit(), Some(2));
+        assert_eq!(stack.push("a"), Ok(()));
+        assert_eq!(stack.push("b"), Ok(()));
+        assert!(stack.is_full());
+        assert_eq!(stack.push("c"), Err(Full("c")));
+        assert_eq!(Full(()).to_string(), "stack is full");
+
+        assert_eq!(stack.pop(), Some("b"));
+        assert_eq!(stack.push("c"), Ok(()));
+        assert_eq!(stack.into_inner().into_iter().collect::<Vec<_>>(), vec!["c", "a"])
+    }
+}
@@ -0,0 +1,477 @@
+use crate::deque::RingDeque;
+
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::iter::FusedIterator;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    left: Option<usize>,
+    right: Option<usize>
+}
+
+/// Order of the values in a tree traversal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// Each value before its subtrees.
+    Preorder,
+    /// Each value between its left and right subtrees.
+    Inorder,
+    /// Each value after its subtrees.
+    Postorder,
+    /// By depth, from left to right.
+    LevelOrder
+}
+
+/// Error from building a [`BinaryTree`] with traversals that don't
+/// describe the same tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraversalError;
+
+impl Display for TraversalError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "traversals don't describe the same binary tree")
+    }
+}
+
+impl Error for TraversalError {}
+
+/// Binary tree without ordering between its values, rebuilt from its
+/// traversals.
+///
+/// Nodes are kept in a single vector, and every traversal uses an explicit
+/// stack or queue, so deep trees can't overflow the call stack.
+#[derive(Clone)]
+pub struct BinaryTree<T> {
+    nodes: Vec<Node<T>>,
+    root: Option<usize>
+}
+
+impl<T> BinaryTree<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new(), root: None }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn root(&self) -> Option<&T> {
+        self.root.map(|index| &self.nodes[index].value)
+    }
+
+    /// Links the nodes in the order of `values`, each one either as the
+    /// left child of the last node or as the right child of the last node
+    /// already closed by `inorder`.
+    ///
+    /// With `mirrored`, the sides are swapped, which rebuilds the tree from
+    /// reversed postorder and inorder sequences.
+    fn build<'a, I>(values: Vec<T>, inorder: I, mirrored: bool) -> Self
+    where T: 'a + PartialEq, I: IntoIterator<Item = &'a T> {
+        let nodes = values.into_iter().map(|value| Node { value, left: None, right: None });
+        let mut nodes: Vec<_> = nodes.collect();
+        let mut inorder = inorder.into_iter().peekable();
+        let mut stack: Vec<usize> = Vec::new();
+
+        for index in 0..nodes.len() {
+            let mut closed = None;
+            while let Some(&top) = stack.last() {
+                if inorder.peek() != Some(&&nodes[top].value) {
+                    break
+                }
+                inorder.next();
+                closed = stack.pop()
+            }
+            match (closed, stack.last()) {
+                (Some(parent), _) => *side(&mut nodes[parent], !mirrored) = Some(index),
+                (None, Some(&parent)) => *side(&mut nodes[parent], mirrored) = Some(index),
+                (None, None) => ()
+            }
+            stack.push(index)
+        }
+        let root = if nodes.is_empty() { None } else { Some(0) };
+        Self { nodes, root }
+    }
+
+    /// Rebuilds a tree from its preorder and inorder traversals.
+    ///
+    /// # Errors
+    ///
+    /// When the sequences don't describe the same tree. With repeated
+    /// values, only one of the possible trees is tried.
+    #[inline]
+    pub fn from_preorder_inorder(preorder: Vec<T>, inorder: &[T]) -> Result<Self, TraversalError>
+    where T: PartialEq {
+        if preorder.len() != inorder.len() {
+            return Err(TraversalError)
+        }
+        let tree = Self::build(preorder, inorder, false);
+        if tree.inorder().eq(inorder) { Ok(tree) } else { Err(TraversalError) }
+    }
+
+    /// Rebuilds a tree from its postorder and inorder traversals.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`from_preorder_inorder`](BinaryTree::from_preorder_inorder).
+    #[inline]
+    pub fn from_postorder_inorder(
+        mut postorder: Vec<T>,
+        inorder: &[T],
+    ) -> Result<Self, TraversalError>
+    where T: PartialEq {
+        if postorder.len() != inorder.len() {
+            return Err(TraversalError)
+        }
+        postorder.reverse();
+        let tree = Self::build(postorder, inorder.iter().rev(), true);
+        if tree.inorder().eq(inorder) { Ok(tree) } else { Err(TraversalError) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn preorder(&self) -> Preorder<'_, T> {
+        let stack = self.root.map(|root| (root, 0)).into_iter().collect();
+        Preorder { nodes: &self.nodes, stack }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn inorder(&self) -> Inorder<'_, T> {
+        Inorder { nodes: &self.nodes, stack: Vec::new(), next: self.root.map(|root| (root, 0)) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn postorder(&self) -> Postorder<'_, T> {
+        let stack = self.root.map(|root| (root, 0, false)).into_iter().collect();
+        Postorder { nodes: &self.nodes, stack }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn level_order(&self) -> LevelOrder<'_, T> {
+        let queue = self.root.map(|root| (root, 0)).into_iter().collect();
+        LevelOrder { nodes: &self.nodes, queue }
+    }
+
+    #[inline]
+    fn traverse(&self, order: Order) -> Traversal<'_, T> {
+        match order {
+            Order::Preorder => Traversal::Pre(self.preorder()),
+            Order::Inorder => Traversal::In(self.inorder()),
+            Order::Postorder => Traversal::Post(self.postorder()),
+            Order::LevelOrder => Traversal::Level(self.level_order())
+        }
+    }
+
+    /// Number of levels in the tree, zero when empty.
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> usize {
+        let mut levels = self.level_order();
+        std::iter::from_fn(|| levels.next_with_depth()).last().map_or(0, |(_, depth)| depth + 1)
+    }
+
+    /// Checks if any value matches `predicate`, in *O*(n).
+    #[inline]
+    pub fn contains_by<F: FnMut(&T) -> bool>(&self, predicate: F) -> bool {
+        self.preorder().any(predicate)
+    }
+
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool where T: PartialEq {
+        self.contains_by(|item| item == value)
+    }
+
+    /// Values of this tree that are also in `other`, in the given order,
+    /// each with its depth in this tree. The root has depth zero.
+    ///
+    /// Takes *O*(n m), as the trees have no order.
+    #[must_use]
+    #[inline]
+    pub fn intersection<'a>(&'a self, other: &Self, order: Order) -> Vec<(&'a T, usize)>
+    where T: PartialEq {
+        let mut traversal = self.traverse(order);
+        let visited = std::iter::from_fn(|| traversal.next_with_depth());
+        visited.filter(|(value, _)| other.contains(value)).collect()
+    }
+}
+
+/// Child of `node` on the left, or on the right with `right`.
+#[inline]
+fn side<T>(node: &mut Node<T>, right: bool) -> &mut Option<usize> {
+    if right { &mut node.right } else { &mut node.left }
+}
+
+/// Any of the traversals, for choosing the order at runtime.
+enum Traversal<'a, T> {
+    Pre(Preorder<'a, T>),
+    In(Inorder<'a, T>),
+    Post(Postorder<'a, T>),
+    Level(LevelOrder<'a, T>)
+}
+
+impl<'a, T> Traversal<'a, T> {
+    #[inline]
+    fn next_with_depth(&mut self) -> Option<(&'a T, usize)> {
+        match self {
+            Self::Pre(iter) => iter.next_with_depth(),
+            Self::In(iter) => iter.next_with_depth(),
+            Self::Post(iter) => iter.next_with_depth(),
+            Self::Level(iter) => iter.next_with_depth()
+        }
+    }
+}
+
+/// Iterator over the values of a [`BinaryTree`] in preorder.
+pub struct Preorder<'a, T> {
+    nodes: &'a [Node<T>],
+    // nodes to visit, with their depths
+    stack: Vec<(usize, usize)>
+}
+
+impl<'a, T> Preorder<'a, T> {
+    /// Next value with its depth in the tree.
+    #[inline]
+    pub fn next_with_depth(&mut self) -> Option<(&'a T, usize)> {
+        let (index, depth) = self.stack.pop()?;
+        let node = &self.nodes[index];
+        self.stack.extend(node.right.map(|right| (right, depth + 1)));
+        self.stack.extend(node.left.map(|left| (left, depth + 1)));
+        Some((&node.value, depth))
+    }
+
+    /// Nodes left on the stack, from the top.
+    #[inline]
+    fn pending(&self) -> impl Iterator<Item = usize> + '_ {
+        self.stack.iter().rev().map(|&(index, _)| index)
+    }
+}
+
+/// Iterator over the values of a [`BinaryTree`] in inorder.
+pub struct Inorder<'a, T> {
+    nodes: &'a [Node<T>],
+    // visited nodes whose left subtrees are being visited
+    stack: Vec<(usize, usize)>,
+    // root of the next subtree to visit
+    next: Option<(usize, usize)>
+}
+
+impl<'a, T> Inorder<'a, T> {
+    /// Next value with its depth in the tree.
+    #[inline]
+    pub fn next_with_depth(&mut self) -> Option<(&'a T, usize)> {
+        while let Some((index, depth)) = self.next {
+            self.stack.push((index, depth));
+            self.next = self.nodes[index].left.map(|left| (left, depth + 1))
+        }
+        let (index, depth) = self.stack.pop()?;
+        let node = &self.nodes[index];
+        self.next = node.right.map(|right| (right, depth + 1));
+        Some((&node.value, depth))
+    }
+
+    /// Root of the next subtree, then the nodes left on the stack from the top.
+    #[inline]
+    fn pending(&self) -> impl Iterator<Item = usize> + '_ {
+        self.next.iter().chain(self.stack.iter().rev()).map(|&(index, _)| index)
+    }
+}
+
+/// Iterator over the values of a [`BinaryTree`] in postorder.
+pub struct Postorder<'a, T> {
+    nodes: &'a [Node<T>],
+    // nodes to visit, with their depths and whether their children were
+    // already pushed
+    stack: Vec<(usize, usize, bool)>
+}
+
+impl<'a, T> Postorder<'a, T> {
+    /// Next value with its depth in the tree.
+    #[inline]
+    pub fn next_with_depth(&mut self) -> Option<(&'a T, usize)> {
+        loop {
+            let (index, depth, expanded) = self.stack.pop()?;
+            let node = &self.nodes[index];
+            if expanded {
+                return Some((&node.value, depth))
+            }
+            self.stack.push((index, depth, true));
+            self.stack.extend(node.right.map(|right| (right, depth + 1, false)));
+            self.stack.extend(node.left.map(|left| (left, depth + 1, false)))
+        }
+    }
+
+    /// Nodes left on the stack, from the top.
+    #[inline]
+    fn pending(&self) -> impl Iterator<Item = usize> + '_ {
+        self.stack.iter().rev().map(|&(index, _, _)| index)
+    }
+}
+
+/// Iterator over the values of a [`BinaryTree`] by level.
+pub struct LevelOrder<'a, T> {
+    nodes: &'a [Node<T>],
+    // nodes to visit, with their depths
+    queue: RingDeque<(usize, usize)>
+}
+
+impl<'a, T> LevelOrder<'a, T> {
+    /// Next value with its depth in the tree.
+    #[inline]
+    pub fn next_with_depth(&mut self) -> Option<(&'a T, usize)> {
+        let (index, depth) = self.queue.pop_front()?;
+        let node = &self.nodes[index];
+        self.queue.extend(node.left.map(|left| (left, depth + 1)));
+        self.queue.extend(node.right.map(|right| (right, depth + 1)));
+        Some((&node.value, depth))
+    }
+
+    /// Nodes left in the queue, from the front.
+    #[inline]
+    fn pending(&self) -> impl Iterator<Item = usize> + '_ {
+        self.queue.iter().map(|&(index, _)| index)
+    }
+}
+
+macro_rules! traversal_iterator {
+    ($name: ident) => {
+        impl<'a, T> Iterator for $name<'a, T> {
+            type Item = &'a T;
+
+            #[inline]
+            fn next(&mut self) -> Option<&'a T> {
+                self.next_with_depth().map(|(value, _)| value)
+            }
+        }
+
+        impl<T> FusedIterator for $name<'_, T> {}
+
+        impl<T: Debug> Debug for $name<'_, T> {
+            #[inline]
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                let pending = self.pending().map(|index| &self.nodes[index].value);
+                f.debug_list().entries(pending).finish()
+            }
+        }
+    };
+}
+
+traversal_iterator!(Preorder);
+traversal_iterator!(Inorder);
+traversal_iterator!(Postorder);
+traversal_iterator!(LevelOrder);
+
+impl<T> Default for BinaryTree<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for BinaryTree<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.level_order()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //        a
+    //      /   \
+    //     b     c
+    //    / \     \
+    //   d   e     f
+    //      /
+    //     g
+    fn example() -> BinaryTree<char> {
+        let inorder: Vec<_> = "dbgeacf".chars().collect();
+        BinaryTree::from_preorder_inorder("abdegcf".chars().collect(), &inorder).unwrap()
+    }
+
+    #[test]
+    fn traversals() {
+        let tree = example();
+        assert_eq!((tree.len(), tree.root(), tree.height()), (7, Some(&'a'), 4));
+        assert_eq!(tree.preorder().collect::<String>(), "abdegcf");
+        assert_eq!(tree.inorder().collect::<String>(), "dbgeacf");
+        assert_eq!(tree.postorder().collect::<String>(), "dgebfca");
+        assert_eq!(tree.level_order().collect::<String>(), "abcdefg");
+        assert_eq!(format!("{:?}", BinaryTree::<u8>::new()), "[]");
+
+        let mut inorder = tree.inorder();
+        inorder.nth(2);
+        assert_eq!(format!("{:?}", inorder), "['e', 'a']");
+        let mut level_order = tree.level_order();
+        level_order.next();
+        assert_eq!(format!("{:?}", level_order), "['b', 'c']")
+    }
+
+    #[test]
+    fn from_postorder() {
+        let inorder: Vec<_> = "dbgeacf".chars().collect();
+        let postorder = "dgebfca".chars().collect();
+        let tree = BinaryTree::from_postorder_inorder(postorder, &inorder).unwrap();
+        assert_eq!(tree.preorder().collect::<String>(), "abdegcf");
+        assert_eq!(tree.level_order().collect::<String>(), "abcdefg")
+    }
+
+    #[test]
+    fn invalid_traversals() {
+        let inorder: Vec<_> = "abc".chars().collect();
+        let preorder = |values: &str| values.chars().collect();
+        let tree = BinaryTree::from_preorder_inorder(preorder("ab"), &inorder);
+        assert_eq!(tree.err(), Some(TraversalError));
+        let tree = BinaryTree::from_preorder_inorder(preorder("axc"), &inorder);
+        assert_eq!(tree.err(), Some(TraversalError));
+        let repeated = ['a', 'b', 'b', 'c'];
+        let postorder = vec!['b', 'c', 'a', 'b'];
+        let tree = BinaryTree::from_postorder_inorder(postorder, &repeated);
+        assert_eq!(tree.err(), Some(TraversalError));
+        assert!(BinaryTree::<u8>::from_postorder_inorder(vec![], &[]).unwrap().is_empty())
+    }
+
+    #[test]
+    fn search_and_intersect() {
+        let tree = example();
+        assert!(tree.contains(&'g') && !tree.contains(&'z'));
+        assert!(tree.contains_by(char::is_ascii_lowercase));
+
+        let inorder: Vec<_> = "zcxg".chars().collect();
+        let other = BinaryTree::from_preorder_inorder("xczg".chars().collect(), &inorder).unwrap();
+        assert_eq!(tree.intersection(&other, Order::Preorder), vec![(&'g', 3), (&'c', 1)]);
+        assert_eq!(tree.intersection(&other, Order::LevelOrder), vec![(&'c', 1), (&'g', 3)]);
+        assert_eq!(other.intersection(&tree, Order::Postorder), vec![(&'c', 1), (&'g', 1)])
+    }
+
+    #[test]
+    fn deep_trees() {
+        let len = 200_000;
+        let values: Vec<_> = (0..len).collect();
+        let left = BinaryTree::from_postorder_inorder(values.clone(), &values).unwrap();
+        let right = BinaryTree::from_preorder_inorder(values.clone(), &values).unwrap();
+        assert_eq!((left.height(), right.height()), (len, len));
+
+        assert!(left.postorder().eq(&values) && right.preorder().eq(&values));
+        assert_eq!(left.preorder().next(), Some(&(len - 1)));
+        assert!(right.clone().level_order().eq(right.inorder()))
+    }
+}
@@ -0,0 +1,695 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
+use std::iter::{FromIterator, FusedIterator};
+use std::ops::{Bound, RangeBounds};
+
+#[derive(Clone)]
+struct Node<K, V> {
+    key: K,
+    value: V,
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>
+}
+
+/// Node with the least key in the subtree of `index`.
+#[inline]
+fn leftmost<K, V>(nodes: &[Node<K, V>], mut index: usize) -> usize {
+    while let Some(left) = nodes[index].left {
+        index = left
+    }
+    index
+}
+
+/// Node with the greatest key in the subtree of `index`.
+#[inline]
+fn rightmost<K, V>(nodes: &[Node<K, V>], mut index: usize) -> usize {
+    while let Some(right) = nodes[index].right {
+        index = right
+    }
+    index
+}
+
+/// Next node in order of the keys.
+#[inline]
+fn successor<K, V>(nodes: &[Node<K, V>], mut index: usize) -> Option<usize> {
+    if let Some(right) = nodes[index].right {
+        return Some(leftmost(nodes, right))
+    }
+    while let Some(parent) = nodes[index].parent {
+        if nodes[parent].left == Some(index) {
+            return Some(parent)
+        }
+        index = parent
+    }
+    None
+}
+
+/// Previous node in order of the keys.
+#[inline]
+fn predecessor<K, V>(nodes: &[Node<K, V>], mut index: usize) -> Option<usize> {
+    if let Some(left) = nodes[index].left {
+        return Some(rightmost(nodes, left))
+    }
+    while let Some(parent) = nodes[index].parent {
+        if nodes[parent].right == Some(index) {
+            return Some(parent)
+        }
+        index = parent
+    }
+    None
+}
+
+/// Ordered map over an unbalanced binary search tree.
+///
+/// Operations take time proportional to the height of the tree, which is
+/// *O*(log n) for keys in random order, but *O*(n) for sorted ones. Nodes
+/// are kept in a single vector with links to their parents, so nothing
+/// here recurses.
+#[derive(Clone)]
+pub struct BstMap<K, V> {
+    nodes: Vec<Node<K, V>>,
+    root: Option<usize>
+}
+
+impl<K, V> BstMap<K, V> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new(), root: None }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Number of levels in the tree, zero when empty.
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack: Vec<_> = self.root.map(|root| (root, 1)).into_iter().collect();
+        while let Some((index, depth)) = stack.pop() {
+            let node = &self.nodes[index];
+            height = height.max(depth);
+            stack.extend(node.left.map(|left| (left, depth + 1)));
+            stack.extend(node.right.map(|right| (right, depth + 1)))
+        }
+        height
+    }
+
+    #[inline]
+    fn entry_at(&self, index: usize) -> (&K, &V) {
+        let node = &self.nodes[index];
+        (&node.key, &node.value)
+    }
+
+    /// Entry with the least key.
+    #[must_use]
+    #[inline]
+    pub fn min(&self) -> Option<(&K, &V)> {
+        self.root.map(|root| self.entry_at(leftmost(&self.nodes, root)))
+    }
+
+    /// Entry with the greatest key.
+    #[must_use]
+    #[inline]
+    pub fn max(&self) -> Option<(&K, &V)> {
+        self.root.map(|root| self.entry_at(rightmost(&self.nodes, root)))
+    }
+
+    /// Iterator over the entries, in order of the keys.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Range<'_, K, V> {
+        Range {
+            nodes: &self.nodes,
+            front: self.root.map(|root| leftmost(&self.nodes, root)),
+            back: self.root.map(|root| rightmost(&self.nodes, root))
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None
+    }
+
+    /// Makes `new` take the place of `old` as a child of `parent`.
+    #[inline]
+    fn replace_child(&mut self, parent: Option<usize>, old: usize, new: Option<usize>) {
+        match parent {
+            None => self.root = new,
+            Some(parent) => {
+                let node = &mut self.nodes[parent];
+                if node.left == Some(old) {
+                    node.left = new
+                } else {
+                    node.right = new
+                }
+            },
+        }
+    }
+
+    #[inline]
+    fn swap_entries(&mut self, a: usize, b: usize) {
+        let (low, high) = self.nodes.split_at_mut(a.max(b));
+        let (x, y) = (&mut low[a.min(b)], &mut high[0]);
+        std::mem::swap(&mut x.key, &mut y.key);
+        std::mem::swap(&mut x.value, &mut y.value)
+    }
+
+    /// Frees the slot of an unlinked node, moving the last node into it.
+    #[inline]
+    fn release(&mut self, index: usize) -> (K, V) {
+        let node = self.nodes.swap_remove(index);
+        if index < self.nodes.len() {
+            let Node { left, right, parent, .. } = self.nodes[index];
+            self.replace_child(parent, self.nodes.len(), Some(index));
+            for child in left.into_iter().chain(right) {
+                self.nodes[child].parent = Some(index)
+            }
+        }
+        (node.key, node.value)
+    }
+
+    /// Removes the node at `index`. With two children, its entry is first
+    /// swapped with the one of its successor, which has at most one child.
+    #[inline]
+    fn remove_at(&mut self, mut index: usize) -> (K, V) {
+        let node = &self.nodes[index];
+        if let (Some(_), Some(right)) = (node.left, node.right) {
+            let next = leftmost(&self.nodes, right);
+            self.swap_entries(index, next);
+            index = next
+        }
+        let Node { left, right, parent, .. } = self.nodes[index];
+        let child = left.or(right);
+        if let Some(child) = child {
+            self.nodes[child].parent = parent
+        }
+        self.replace_child(parent, index, child);
+        self.release(index)
+    }
+}
+
+impl<K: Ord, V> BstMap<K, V> {
+    /// Node with `key`, or the parent where it would be inserted and
+    /// whether as its right child.
+    #[inline]
+    fn search<Q: ?Sized + Ord>(&self, key: &Q) -> Result<usize, Option<(usize, bool)>>
+    where K: Borrow<Q> {
+        let mut parent = None;
+        let mut next = self.root;
+        while let Some(index) = next {
+            let node = &self.nodes[index];
+            let right = match key.cmp(node.key.borrow()) {
+                Ordering::Equal => return Ok(index),
+                Ordering::Less => false,
+                Ordering::Greater => true
+            };
+            parent = Some((index, right));
+            next = if right { node.right } else { node.left }
+        }
+        Err(parent)
+    }
+
+    /// First node inside the lower bound `bound`.
+    #[inline]
+    fn lower_bound<Q: ?Sized + Ord>(&self, bound: Bound<&Q>) -> Option<usize>
+    where K: Borrow<Q> {
+        let (mut found, mut next) = (None, self.root);
+        while let Some(index) = next {
+            let node = &self.nodes[index];
+            let inside = match bound {
+                Bound::Included(key) => node.key.borrow() >= key,
+                Bound::Excluded(key) => node.key.borrow() > key,
+                Bound::Unbounded => true
+            };
+            if inside {
+                found = Some(index);
+                next = node.left
+            } else {
+                next = node.right
+            }
+        }
+        found
+    }
+
+    /// Last node inside the upper bound `bound`.
+    #[inline]
+    fn upper_bound<Q: ?Sized + Ord>(&self, bound: Bound<&Q>) -> Option<usize>
+    where K: Borrow<Q> {
+        let (mut found, mut next) = (None, self.root);
+        while let Some(index) = next {
+            let node = &self.nodes[index];
+            let inside = match bound {
+                Bound::Included(key) => node.key.borrow() <= key,
+                Bound::Excluded(key) => node.key.borrow() < key,
+                Bound::Unbounded => true
+            };
+            if inside {
+                found = Some(index);
+                next = node.right
+            } else {
+                next = node.left
+            }
+        }
+        found
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&V>
+    where K: Borrow<Q> {
+        self.search(key).ok().map(|index| &self.nodes[index].value)
+    }
+
+    #[inline]
+    pub fn get_mut<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<&mut V>
+    where K: Borrow<Q> {
+        let index = self.search(key).ok()?;
+        Some(&mut self.nodes[index].value)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn contains_key<Q: ?Sized + Ord>(&self, key: &Q) -> bool
+    where K: Borrow<Q> {
+        self.search(key).is_ok()
+    }
+
+    /// Entry with the greatest key less than or equal to `key`.
+    #[must_use]
+    #[inline]
+    pub fn floor<Q: ?Sized + Ord>(&self, key: &Q) -> Option<(&K, &V)>
+    where K: Borrow<Q> {
+        self.upper_bound(Bound::Included(key)).map(|index| self.entry_at(index))
+    }
+
+    /// Entry with the least key greater than or equal to `key`.
+    #[must_use]
+    #[inline]
+    pub fn ceil<Q: ?Sized + Ord>(&self, key: &Q) -> Option<(&K, &V)>
+    where K: Borrow<Q> {
+        self.lower_bound(Bound::Included(key)).map(|index| self.entry_at(index))
+    }
+
+    /// Iterator over the entries with keys in `range`, from both ends.
+    #[inline]
+    pub fn range<Q: ?Sized + Ord, R: RangeBounds<Q>>(&self, range: R) -> Range<'_, K, V>
+    where K: Borrow<Q> {
+        let front = self.lower_bound(range.start_bound());
+        let back = self.upper_bound(range.end_bound());
+        match (front, back) {
+            (Some(first), Some(last)) if self.nodes[first].key <= self.nodes[last].key => {
+                Range { nodes: &self.nodes, front, back }
+            },
+            _ => Range { nodes: &self.nodes, front: None, back: None }
+        }
+    }
+
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.search(&key) {
+            Ok(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            Err(parent) => Entry::Vacant(VacantEntry { map: self, key, parent })
+        }
+    }
+
+    /// Inserts `value` with `key`, returning the old value for the key.
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            },
+        }
+    }
+
+    #[inline]
+    pub fn remove<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<V>
+    where K: Borrow<Q> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    #[inline]
+    pub fn remove_entry<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<(K, V)>
+    where K: Borrow<Q> {
+        let index = self.search(key).ok()?;
+        Some(self.remove_at(index))
+    }
+}
+
+/// View into a single key of a [`BstMap`].
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>)
+}
+
+/// Entry for a key in the map.
+pub struct OccupiedEntry<'a, K, V> {
+    map: &'a mut BstMap<K, V>,
+    index: usize
+}
+
+/// Entry for a key missing from the map.
+pub struct VacantEntry<'a, K, V> {
+    map: &'a mut BstMap<K, V>,
+    key: K,
+    // where the node will be linked
+    parent: Option<(usize, bool)>
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(entry) => entry.key(),
+            Self::Vacant(entry) => entry.key()
+        }
+    }
+
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    #[inline]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(default())
+        }
+    }
+
+    #[inline]
+    pub fn or_default(self) -> &'a mut V where V: Default {
+        self.or_insert_with(V::default)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(entry) = &mut self {
+            f(entry.get_mut())
+        }
+        self
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.map.nodes[self.index].key
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get(&self) -> &V {
+        &self.map.nodes[self.index].value
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.nodes[self.index].value
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.nodes[self.index].value
+    }
+
+    /// Replaces the value, returning the old one.
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_at(self.index)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    #[must_use]
+    #[inline]
+    pub const fn key(&self) -> &K {
+        &self.key
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Links a new leaf with the key and `value`.
+    #[inline]
+    pub fn insert(self, value: V) -> &'a mut V {
+        let Self { map, key, parent } = self;
+        let index = map.nodes.len();
+        let up = parent.map(|(parent, _)| parent);
+        let node = Node { key, value, left: None, right: None, parent: up };
+        map.nodes.push(node);
+        match parent {
+            None => map.root = Some(index),
+            Some((parent, false)) => map.nodes[parent].left = Some(index),
+            Some((parent, true)) => map.nodes[parent].right = Some(index)
+        }
+        &mut map.nodes[index].value
+    }
+}
+
+/// Iterator over the entries of a [`BstMap`] in order, from both ends.
+pub struct Range<'a, K, V> {
+    nodes: &'a [Node<K, V>],
+    // next nodes on each end, both 'None' when done
+    front: Option<usize>,
+    back: Option<usize>
+}
+
+impl<K, V> Clone for Range<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { nodes: self.nodes, front: self.front, back: self.back }
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.front?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None
+        } else {
+            self.front = successor(self.nodes, index)
+        }
+        let node = &self.nodes[index];
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Range<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.back?;
+        if self.front == self.back {
+            self.front = None;
+            self.back = None
+        } else {
+            self.back = predecessor(self.nodes, index)
+        }
+        let node = &self.nodes[index];
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K, V> FusedIterator for Range<'_, K, V> {}
+
+impl<K: Debug, V: Debug> Debug for Range<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<K, V> Default for BstMap<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for BstMap<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for BstMap<K, V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for BstMap<K, V> {}
+
+impl<K: Ord, V> Extend<(K, V)> for BstMap<K, V> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for BstMap<K, V> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a BstMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Range<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::XorShift;
+    use std::collections::BTreeMap;
+
+    fn is_valid<K: Ord, V>(map: &BstMap<K, V>) -> bool {
+        let linked = map.nodes.iter().enumerate().all(|(index, node)| {
+            let mut children = node.left.into_iter().chain(node.right);
+            children.all(|child| map.nodes[child].parent == Some(index))
+        });
+        let sorted = map.iter().zip(map.iter().skip(1)).all(|((a, _), (b, _))| a < b);
+        linked && sorted && map.iter().count() == map.len()
+    }
+
+    #[test]
+    fn insert_remove_and_search() {
+        let keys = [50, 30, 70, 20, 40, 60, 80];
+        let mut map: BstMap<_, _> = keys.iter().map(|&key| (key, key / 10)).collect();
+        assert_eq!((map.len(), map.height()), (7, 3));
+        assert_eq!(map.insert(40, 0), Some(4));
+        assert_eq!((map.get(&40), map.get(&45)), (Some(&0), None));
+        assert_eq!((map.floor(&45), map.ceil(&45)), (Some((&40, &0)), Some((&50, &5))));
+        assert_eq!((map.floor(&10), map.ceil(&90)), (None, None));
+        assert_eq!((map.min(), map.max()), (Some((&20, &2)), Some((&80, &8))));
+
+        // the root has two children, so it takes the entry of its successor
+        assert_eq!(map.remove(&50), Some(5));
+        assert_eq!(map.remove(&50), None);
+        assert_eq!(map.remove(&20), Some(2));
+        assert!(is_valid(&map));
+        assert_eq!(format!("{:?}", map), "{30: 3, 40: 0, 60: 6, 70: 7, 80: 8}");
+        map.clear();
+        assert!(map.is_empty() && map.min().is_none())
+    }
+
+    fn keys<'a, I: Iterator<Item = (&'a i32, &'a ())>>(range: I) -> Vec<i32> {
+        range.map(|(&key, ())| key).collect()
+    }
+
+    #[test]
+    fn ranges() {
+        let map: BstMap<_, _> = (0..20).map(|key| ((key * 7) % 20, ())).collect();
+        assert_eq!(keys(map.range(5..=8)), [5, 6, 7, 8]);
+        assert_eq!(keys(map.range(15..).rev()), [19, 18, 17, 16, 15]);
+        assert_eq!(keys(map.range(..2)), [0, 1]);
+        assert!(keys(map.range(8..8)).is_empty() && keys(map.range(30..)).is_empty());
+
+        let mut range = map.range(3..7);
+        assert_eq!(range.next(), Some((&3, &())));
+        assert_eq!(range.next_back(), Some((&6, &())));
+        assert_eq!(range.next_back(), Some((&5, &())));
+        assert_eq!(range.next(), Some((&4, &())));
+        assert_eq!((range.next(), range.next_back()), (None, None))
+    }
+
+    #[test]
+    fn entries() {
+        let mut counts = BstMap::new();
+        for word in "the cat and the hat and the bat".split(' ') {
+            *counts.entry(word).or_default() += 1
+        }
+        assert_eq!(counts.get("the"), Some(&3));
+        counts.entry("cat").and_modify(|count| *count += 10).or_insert(0);
+        assert_eq!(counts.entry("dog").key(), &"dog");
+        assert_eq!(counts.len(), 5);
+
+        if let Entry::Occupied(entry) = counts.entry("and") {
+            assert_eq!(entry.remove_entry(), ("and", 2))
+        }
+        assert_eq!(counts.iter().map(|(_, &count)| count).collect::<Vec<_>>(), [1, 11, 1, 3])
+    }
+
+    #[test]
+    fn random_operations() {
+        let mut rng = XorShift::new(24);
+        let mut map = BstMap::new();
+        let mut model = BTreeMap::new();
+
+        for step in 0..4000 {
+            let key = rng.below(200);
+            match rng.below(3) {
+                0 => assert_eq!(map.remove(&key), model.remove(&key)),
+                _ => assert_eq!(map.insert(key, step), model.insert(key, step))
+            }
+            let end = key + rng.below(50);
+            assert!(map.range(key..end).eq(model.range(key..end)));
+            assert!(map.range(key..=end).rev().eq(model.range(key..=end).rev()));
+            assert_eq!(map.floor(&key), model.range(..=key).next_back());
+            assert_eq!(map.ceil(&key), model.range(key..).next());
+        }
+        assert!(is_valid(&map));
+        assert!(map.iter().eq(model.iter()))
+    }
+
+    #[test]
+    fn sorted_keys() {
+        let map: BstMap<_, _> = (0..2000).map(|key| (key, key)).collect();
+        assert_eq!(map.height(), 2000);
+        assert_eq!(map.range(1990..).count(), 10);
+        assert_eq!(map.clone(), map)
+    }
+}
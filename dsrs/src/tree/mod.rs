@@ -0,0 +1,8 @@
+//! Binary trees.
+mod binary_tree;
+mod bst_map;
+pub mod treap;
+
+pub use binary_tree::{BinaryTree, Inorder, LevelOrder, Order, Postorder, Preorder, TraversalError};
+pub use bst_map::{BstMap, Entry, OccupiedEntry, Range, VacantEntry};
+pub use treap::{ImplicitTreap, Treap};
@@ -0,0 +1,403 @@
+use super::node::{self, merge, size, split_at, Link, Node, Nodes};
+use crate::random::XorShift;
+
+use std::fmt::{self, Debug, Formatter};
+use std::iter::{FromIterator, FusedIterator};
+use std::ops::{Bound, Index, RangeBounds};
+
+/// Sequence over a treap with implicit keys: each value is ordered by its
+/// position, which is the size of everything before it in the tree.
+///
+/// Inserting and removing anywhere, cutting out a range or reversing one
+/// all take *O*(log n) with high probability. Reversals are lazy, they
+/// only mark the root of the range and are applied on the way down.
+#[derive(Clone)]
+pub struct ImplicitTreap<T> {
+    root: Link<T>,
+    rng: XorShift
+}
+
+/// Start and end of `range` in a sequence of length `len`.
+///
+/// # Panics
+///
+/// If the range is decreasing or goes past `len`.
+#[inline]
+fn bounds<R: RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len
+    };
+    assert!(
+        start <= end && end <= len,
+        "range {}..{} out of bounds for length {}",
+        start,
+        end,
+        len
+    );
+    (start, end)
+}
+
+impl<T> ImplicitTreap<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_seed(XorShift::DEFAULT_SEED)
+    }
+
+    /// Creates an empty sequence, whose priorities are generated from
+    /// `seed`.
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { root: None, rng: XorShift::new(seed) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Number of levels in the tree, zero when empty.
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> usize {
+        node::height(&self.root)
+    }
+
+    /// Value at `index`, in *O*(log n).
+    #[must_use]
+    #[inline]
+    pub fn get(&self, mut index: usize) -> Option<&T> {
+        let (mut next, mut flipped) = (self.root.as_deref(), false);
+        while let Some(node) = next {
+            flipped ^= node.reversed;
+            let (first, second) =
+                if flipped { (&node.right, &node.left) } else { (&node.left, &node.right) };
+            let before = size(first);
+            if index == before {
+                return Some(&node.item)
+            }
+            next = if index < before {
+                first.as_deref()
+            } else {
+                index -= before + 1;
+                second.as_deref()
+            }
+        }
+        None
+    }
+
+    /// Value at `index`, applying the reversals on its path.
+    #[inline]
+    pub fn get_mut(&mut self, mut index: usize) -> Option<&mut T> {
+        let mut next = self.root.as_deref_mut();
+        while let Some(node) = next {
+            node.push();
+            let before = size(&node.left);
+            if index == before {
+                return Some(&mut node.item)
+            }
+            next = if index < before {
+                node.left.as_deref_mut()
+            } else {
+                index -= before + 1;
+                node.right.as_deref_mut()
+            }
+        }
+        None
+    }
+
+    /// Splits the tree in the values before `start`, the ones in
+    /// `start..end` and the ones after.
+    #[inline]
+    fn cut(&mut self, start: usize, end: usize) -> (Link<T>, Link<T>, Link<T>) {
+        let (rest, right) = split_at(self.root.take(), end);
+        let (left, middle) = split_at(rest, start);
+        (left, middle, right)
+    }
+
+    /// Inserts `value` so that it ends up at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length.
+    #[inline]
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "index {} out of bounds for length {}", index, self.len());
+        let (left, right) = split_at(self.root.take(), index);
+        self.root = merge(merge(left, Some(Node::new(value, self.rng.next_u64()))), right)
+    }
+
+    /// Appends `value` at the end.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        let node = Node::new(value, self.rng.next_u64());
+        self.root = merge(self.root.take(), Some(node))
+    }
+
+    /// Removes the value at `index`, if any.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None
+        }
+        let (left, middle, right) = self.cut(index, index + 1);
+        self.root = merge(left, right);
+        middle.map(|node| node.item)
+    }
+
+    /// Moves the values from `at` on to a new sequence.
+    ///
+    /// # Panics
+    ///
+    /// If `at` is greater than the length.
+    #[must_use]
+    #[inline]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "index {} out of bounds for length {}", at, self.len());
+        let (left, right) = split_at(self.root.take(), at);
+        self.root = left;
+        Self { root: right, rng: XorShift::new(self.rng.next_u64()) }
+    }
+
+    /// Moves every value of `other` to the end of this sequence, leaving
+    /// it empty.
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        self.root = merge(self.root.take(), other.root.take())
+    }
+
+    /// Drops the values in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is decreasing or goes past the end.
+    #[inline]
+    pub fn erase<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = bounds(&range, self.len());
+        let (left, _, right) = self.cut(start, end);
+        self.root = merge(left, right)
+    }
+
+    /// Reverses the order of the values in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is decreasing or goes past the end.
+    #[inline]
+    pub fn reverse<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = bounds(&range, self.len());
+        let (left, mut middle, right) = self.cut(start, end);
+        if let Some(node) = &mut middle {
+            node.reversed = !node.reversed
+        }
+        self.root = merge(merge(left, middle), right)
+    }
+
+    /// Iterator over the values, in order.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> ImplicitIter<'_, T> {
+        ImplicitIter { nodes: Nodes::new(&self.root) }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.root = None
+    }
+}
+
+impl<T> Index<usize> for ImplicitTreap<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> Default for ImplicitTreap<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for ImplicitTreap<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for ImplicitTreap<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for ImplicitTreap<T> {}
+
+impl<T> Extend<T> for ImplicitTreap<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value)
+        }
+    }
+}
+
+impl<T> FromIterator<T> for ImplicitTreap<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sequence = Self::new();
+        sequence.extend(iter);
+        sequence
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ImplicitTreap<T> {
+    type Item = &'a T;
+    type IntoIter = ImplicitIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the values of an [`ImplicitTreap`], in order.
+pub struct ImplicitIter<'a, T> {
+    nodes: Nodes<'a, T>
+}
+
+impl<'a, T> Iterator for ImplicitIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.nodes.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for ImplicitIter<'_, T> {}
+
+impl<T> FusedIterator for ImplicitIter<'_, T> {}
+
+impl<T> Clone for ImplicitIter<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { nodes: self.nodes.clone() }
+    }
+}
+
+impl<T: Debug> Debug for ImplicitIter<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_by_position() {
+        let mut sequence: ImplicitTreap<_> = "treap".chars().collect();
+        sequence.insert(0, 'a');
+        sequence.insert(6, 's');
+        assert_eq!(sequence.iter().collect::<String>(), "atreaps");
+        assert_eq!((sequence.get(1), sequence[6], sequence.get(7)), (Some(&'t'), 's', None));
+
+        sequence.reverse(1..5);
+        assert_eq!(sequence.iter().collect::<String>(), "aaertps");
+        *sequence.get_mut(2).unwrap() = 'i';
+        sequence.erase(..=1);
+        assert_eq!(sequence.remove(3), Some('p'));
+        assert_eq!(sequence.remove(3), Some('s'));
+        assert_eq!(sequence.remove(3), None);
+        assert_eq!(format!("{:?}", sequence), "['i', 'r', 't']");
+
+        let mut tail = sequence.split_off(1);
+        tail.reverse(..);
+        sequence.append(&mut tail);
+        assert!(tail.is_empty() && sequence.iter().eq(&['i', 't', 'r']))
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn erase_past_end() {
+        let mut sequence: ImplicitTreap<_> = (0..4).collect();
+        sequence.erase(2..5)
+    }
+
+    #[test]
+    fn random_operations() {
+        let mut rng = XorShift::new(9);
+        let mut sequence = ImplicitTreap::with_seed(9);
+        let mut model = Vec::new();
+
+        for value in 0..3000 {
+            let index = rng.below(model.len() + 1);
+            let end = index + rng.below(model.len() - index + 1);
+            match rng.below(5) {
+                0 => {
+                    sequence.erase(index..end);
+                    model.drain(index..end);
+                },
+                1 => {
+                    sequence.reverse(index..end);
+                    model[index..end].reverse()
+                },
+                2 if index < model.len() => {
+                    assert_eq!(sequence.remove(index), Some(model.remove(index)))
+                },
+                2 => assert_eq!(sequence.remove(index), None),
+                _ => {
+                    sequence.insert(index, value);
+                    model.insert(index, value)
+                },
+            }
+            assert_eq!(sequence.get(index), model.get(index));
+        }
+        assert_eq!(sequence.len(), model.len());
+        assert!(sequence.iter().eq(&model));
+        assert!((0..model.len()).all(|index| sequence.get_mut(index) == Some(&mut model[index])))
+    }
+
+    #[test]
+    fn long_reversals() {
+        let len = 100_000;
+        let mut sequence: ImplicitTreap<_> = (0..len).collect();
+        for start in 0..len / 2 {
+            sequence.reverse(start..len - start)
+        }
+        // values at an even distance from the nearest end were flipped an
+        // odd number of times, ending up in the mirrored position
+        let mirrored = |index: usize| index.min(len - 1 - index) % 2 == 0;
+        let expected = (0..len).map(|index| if mirrored(index) { len - 1 - index } else { index });
+        assert!(sequence.iter().copied().eq(expected));
+        assert!(sequence.height() < 100)
+    }
+}
@@ -0,0 +1,416 @@
+use super::node::{self, merge, size, split, Link, Node, Nodes};
+use crate::random::XorShift;
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
+use std::iter::{FromIterator, FusedIterator};
+
+type Root<K, V> = Link<(K, V)>;
+
+/// Ordered map over a treap, a binary search tree that is also a max-heap
+/// of random priorities, which keeps it balanced with high probability.
+///
+/// Every update is a [`split`](Treap::split) followed by merges, and each
+/// node knows the size of its subtree, which gives order statistics in
+/// *O*(log n).
+///
+/// The priorities come from a seedable [`XorShift`], so the shape of the
+/// tree is reproducible.
+#[derive(Clone)]
+pub struct Treap<K, V> {
+    root: Root<K, V>,
+    rng: XorShift
+}
+
+impl<K, V> Treap<K, V> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_seed(XorShift::DEFAULT_SEED)
+    }
+
+    /// Creates an empty map, whose priorities are generated from `seed`.
+    #[must_use]
+    #[inline]
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { root: None, rng: XorShift::new(seed) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Number of levels in the tree, zero when empty.
+    #[must_use]
+    #[inline]
+    pub fn height(&self) -> usize {
+        node::height(&self.root)
+    }
+
+    /// Entry with the least key.
+    #[must_use]
+    #[inline]
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left
+        }
+        Some((&node.item.0, &node.item.1))
+    }
+
+    /// Entry with the greatest key.
+    #[must_use]
+    #[inline]
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right
+        }
+        Some((&node.item.0, &node.item.1))
+    }
+
+    /// Entry with `index` smaller keys, in *O*(log n).
+    #[must_use]
+    #[inline]
+    pub fn nth(&self, mut index: usize) -> Option<(&K, &V)> {
+        let mut next = self.root.as_deref();
+        while let Some(node) = next {
+            let left = size(&node.left);
+            next = match index.cmp(&left) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Equal => return Some((&node.item.0, &node.item.1)),
+                Ordering::Greater => {
+                    index -= left + 1;
+                    node.right.as_deref()
+                },
+            }
+        }
+        None
+    }
+
+    /// Iterator over the entries, in order of the keys.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter { nodes: Nodes::new(&self.root) }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.root = None
+    }
+}
+
+impl<K: Ord, V> Treap<K, V> {
+    #[inline]
+    fn find<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&Node<(K, V)>>
+    where K: Borrow<Q> {
+        let mut next = self.root.as_deref();
+        while let Some(node) = next {
+            next = match key.cmp(node.item.0.borrow()) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Equal => return Some(node),
+                Ordering::Greater => node.right.as_deref()
+            }
+        }
+        None
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn get<Q: ?Sized + Ord>(&self, key: &Q) -> Option<&V>
+    where K: Borrow<Q> {
+        self.find(key).map(|node| &node.item.1)
+    }
+
+    #[inline]
+    pub fn get_mut<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<&mut V>
+    where K: Borrow<Q> {
+        let mut next = self.root.as_deref_mut();
+        while let Some(node) = next {
+            next = match key.cmp(node.item.0.borrow()) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.item.1),
+                Ordering::Greater => node.right.as_deref_mut()
+            }
+        }
+        None
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn contains_key<Q: ?Sized + Ord>(&self, key: &Q) -> bool
+    where K: Borrow<Q> {
+        self.find(key).is_some()
+    }
+
+    /// Number of keys less than `key`, in *O*(log n).
+    #[must_use]
+    #[inline]
+    pub fn rank<Q: ?Sized + Ord>(&self, key: &Q) -> usize
+    where K: Borrow<Q> {
+        let (mut rank, mut next) = (0, self.root.as_deref());
+        while let Some(node) = next {
+            if node.item.0.borrow() < key {
+                rank += size(&node.left) + 1;
+                next = node.right.as_deref()
+            } else {
+                next = node.left.as_deref()
+            }
+        }
+        rank
+    }
+
+    /// Splits the tree in the keys less than `key` and the rest.
+    #[inline]
+    fn split_before<Q: ?Sized + Ord>(&mut self, key: &Q) -> (Root<K, V>, Root<K, V>)
+    where K: Borrow<Q> {
+        split(self.root.take(), &mut |(item, _): &(K, V), _| item.borrow() < key)
+    }
+
+    /// Moves the entries with keys greater than or equal to `key` to a new
+    /// map, in *O*(log n).
+    #[must_use]
+    #[inline]
+    pub fn split<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
+    where K: Borrow<Q> {
+        let (left, right) = self.split_before(key);
+        self.root = left;
+        Self { root: right, rng: XorShift::new(self.rng.next_u64()) }
+    }
+
+    /// Moves every entry of `other` into this map, in *O*(log n).
+    ///
+    /// # Panics
+    ///
+    /// If some key in `other` is not greater than every key in this map.
+    #[inline]
+    pub fn merge(&mut self, other: Self) {
+        if let (Some((last, _)), Some((first, _))) = (self.max(), other.min()) {
+            assert!(last < first, "the keys of the maps overlap");
+        }
+        self.root = merge(self.root.take(), other.root)
+    }
+
+    /// Inserts `value` with `key`, returning the old value for the key.
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(std::mem::replace(old, value))
+        }
+        let node = Node::new((key, value), self.rng.next_u64());
+        let (left, right) = self.split_before(&node.item.0);
+        self.root = merge(merge(left, Some(node)), right);
+        None
+    }
+
+    #[inline]
+    pub fn remove<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<V>
+    where K: Borrow<Q> {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    #[inline]
+    pub fn remove_entry<Q: ?Sized + Ord>(&mut self, key: &Q) -> Option<(K, V)>
+    where K: Borrow<Q> {
+        let (left, rest) = self.split_before(key);
+        let (middle, right) = split(rest, &mut |(item, _): &(K, V), _| item.borrow() <= key);
+        self.root = merge(left, right);
+        middle.map(|node| node.item)
+    }
+}
+
+impl<K, V> Default for Treap<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Treap<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for Treap<K, V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for Treap<K, V> {}
+
+impl<K: Ord, V> Extend<(K, V)> for Treap<K, V> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for Treap<K, V> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Treap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a [`Treap`], in order.
+pub struct Iter<'a, K, V> {
+    nodes: Nodes<'a, (K, V)>
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(|(key, value)| (key, value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { nodes: self.nodes.clone() }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Iter<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn is_valid<K: Ord, V>(link: &Root<K, V>) -> bool {
+        let mut stack: Vec<_> = link.iter().collect();
+        while let Some(node) = stack.pop() {
+            for child in node.left.iter().chain(node.right.iter()) {
+                if child.priority > node.priority || child.reversed {
+                    return false
+                }
+                stack.push(child)
+            }
+            if node.size != 1 + size(&node.left) + size(&node.right) {
+                return false
+            }
+        }
+        let nodes = Nodes::new(link);
+        nodes.clone().zip(nodes.skip(1)).all(|((a, _), (b, _))| a < b)
+    }
+
+    #[test]
+    fn insert_remove_and_search() {
+        let mut map: Treap<_, _> = "treap".chars().zip(0..).collect();
+        assert_eq!(map.insert('e', 9), Some(2));
+        assert_eq!((map.len(), map.get(&'e'), map.get(&'x')), (5, Some(&9), None));
+        assert_eq!((map.min(), map.max()), (Some((&'a', &3)), Some((&'t', &0))));
+        assert_eq!(format!("{:?}", map), "{'a': 3, 'e': 9, 'p': 4, 'r': 1, 't': 0}");
+
+        assert_eq!((map.nth(1), map.nth(5)), (Some((&'e', &9)), None));
+        assert_eq!((map.rank(&'a'), map.rank(&'f'), map.rank(&'z')), (0, 2, 5));
+        assert_eq!(map.remove(&'e'), Some(9));
+        assert_eq!(map.remove(&'e'), None);
+        assert!(is_valid(&map.root));
+        map.clear();
+        assert!(map.is_empty() && map.min().is_none())
+    }
+
+    #[test]
+    fn split_and_merge() {
+        let mut map: Treap<_, _> = (0..100).map(|key| (key, key * key)).collect();
+        let mut high = map.split(&60);
+        assert_eq!((map.len(), high.len()), (60, 40));
+        assert_eq!((map.max(), high.min()), (Some((&59, &3481)), Some((&60, &3600))));
+        assert!(is_valid(&map.root) && is_valid(&high.root));
+
+        let empty = high.split(&1000);
+        high.merge(empty);
+        map.merge(high);
+        assert!(map.iter().map(|(&key, _)| key).eq(0..100));
+        assert!(is_valid(&map.root))
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap")]
+    fn merge_overlapping() {
+        let mut low: Treap<_, _> = vec![(1, ()), (5, ())].into_iter().collect();
+        low.merge(vec![(3, ())].into_iter().collect())
+    }
+
+    #[test]
+    fn random_operations() {
+        let mut rng = XorShift::new(25);
+        let mut map = Treap::with_seed(7);
+        let mut model = BTreeMap::new();
+
+        for step in 0..3000 {
+            let key = rng.below(300);
+            match rng.below(3) {
+                0 => assert_eq!(map.remove(&key), model.remove(&key)),
+                _ => assert_eq!(map.insert(key, step), model.insert(key, step))
+            }
+            let rank = model.range(..key).count();
+            assert_eq!(map.rank(&key), rank);
+            assert_eq!(map.nth(rank), model.range(key..).next());
+        }
+        assert!(is_valid(&map.root));
+        assert!(map.iter().eq(model.iter()));
+        assert_eq!(map.iter().len(), model.len())
+    }
+
+    #[test]
+    fn balanced_and_reproducible() {
+        let map: Treap<_, _> = (0..100_000).map(|key| (key, ())).collect();
+        // the expected height is about 3 log n, for log n ≈ 17
+        assert!(map.height() < 100, "height {} is too large", map.height());
+
+        let shape = |seed| {
+            let mut map = Treap::with_seed(seed);
+            map.extend((0..100).map(|key| (key, ())));
+            map.height()
+        };
+        assert_eq!(shape(3), shape(3));
+        assert_eq!(map.clone(), map)
+    }
+}
@@ -0,0 +1,7 @@
+//! Treaps, binary search trees balanced by random priorities.
+mod node;
+mod map;
+mod implicit;
+
+pub use map::{Iter, Treap};
+pub use implicit::{ImplicitIter, ImplicitTreap};
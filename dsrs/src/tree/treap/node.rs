@@ -0,0 +1,171 @@
+//! Nodes shared by both treaps, and the split and merge operations that
+//! everything else is built on.
+pub(super) type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Clone)]
+pub(super) struct Node<T> {
+    pub(super) item: T,
+    // nodes with greater priority are closer to the root
+    pub(super) priority: u64,
+    // number of nodes in the subtree
+    pub(super) size: usize,
+    // whether the children, and all of their descendants, must be swapped
+    // before use, only set by sequences
+    pub(super) reversed: bool,
+    pub(super) left: Link<T>,
+    pub(super) right: Link<T>
+}
+
+impl<T> Node<T> {
+    #[inline]
+    pub(super) fn new(item: T, priority: u64) -> Box<Self> {
+        Box::new(Self { item, priority, size: 1, reversed: false, left: None, right: None })
+    }
+
+    /// Recomputes the size after the children changed.
+    #[inline]
+    fn update(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right)
+    }
+
+    /// Applies a pending reversal, moving it down to the children.
+    #[inline]
+    pub(super) fn push(&mut self) {
+        if std::mem::replace(&mut self.reversed, false) {
+            std::mem::swap(&mut self.left, &mut self.right);
+            for child in self.left.iter_mut().chain(self.right.iter_mut()) {
+                child.reversed = !child.reversed
+            }
+        }
+    }
+}
+
+#[inline]
+pub(super) fn size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+/// Joins two trees, with every node of `left` before the ones of `right`.
+///
+/// Recurses once per level, which is *O*(log n) with high probability.
+#[inline]
+pub(super) fn merge<T>(left: Link<T>, right: Link<T>) -> Link<T> {
+    match (left, right) {
+        (None, link) | (link, None) => link,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority >= b.priority {
+                a.push();
+                a.right = merge(a.right.take(), Some(b));
+                a.update();
+                Some(a)
+            } else {
+                b.push();
+                b.left = merge(Some(a), b.left.take());
+                b.update();
+                Some(b)
+            }
+        },
+    }
+}
+
+/// Splits a tree in the nodes for which `goes_left` is true and the rest.
+///
+/// `goes_left` gets each node on a path from the root, with the size of
+/// its left subtree, and must be true for a prefix of the nodes in order.
+#[inline]
+pub(super) fn split<T, F>(link: Link<T>, goes_left: &mut F) -> (Link<T>, Link<T>)
+where F: FnMut(&T, usize) -> bool {
+    let mut node = match link {
+        None => return (None, None),
+        Some(node) => node
+    };
+    node.push();
+    if goes_left(&node.item, size(&node.left)) {
+        let (middle, right) = split(node.right.take(), goes_left);
+        node.right = middle;
+        node.update();
+        (Some(node), right)
+    } else {
+        let (left, middle) = split(node.left.take(), goes_left);
+        node.left = middle;
+        node.update();
+        (left, Some(node))
+    }
+}
+
+/// Splits a tree in its first `index` nodes and the rest.
+#[inline]
+pub(super) fn split_at<T>(link: Link<T>, index: usize) -> (Link<T>, Link<T>) {
+    let mut remaining = index;
+    split(link, &mut |_, left| {
+        let goes_left = left < remaining;
+        if goes_left {
+            remaining -= left + 1
+        }
+        goes_left
+    })
+}
+
+/// Number of levels in a tree, zero when empty.
+#[inline]
+pub(super) fn height<T>(root: &Link<T>) -> usize {
+    let mut height = 0;
+    let mut stack: Vec<_> = root.iter().map(|node| (node, 1)).collect();
+    while let Some((node, depth)) = stack.pop() {
+        height = height.max(depth);
+        stack.extend(node.left.iter().chain(node.right.iter()).map(|child| (child, depth + 1)))
+    }
+    height
+}
+
+/// In order traversal of a tree, accounting for pending reversals.
+pub(super) struct Nodes<'a, T> {
+    // nodes whose left subtrees are being visited, and whether their
+    // children are swapped
+    stack: Vec<(&'a Node<T>, bool)>,
+    remaining: usize
+}
+
+impl<'a, T> Nodes<'a, T> {
+    #[inline]
+    pub(super) fn new(root: &'a Link<T>) -> Self {
+        let mut nodes = Self { stack: Vec::new(), remaining: size(root) };
+        nodes.descend(root.as_deref(), false);
+        nodes
+    }
+
+    /// Stacks the path to the first node in the subtree of `next`.
+    #[inline]
+    fn descend(&mut self, mut next: Option<&'a Node<T>>, flipped: bool) {
+        let mut flipped = flipped;
+        while let Some(node) = next {
+            flipped ^= node.reversed;
+            self.stack.push((node, flipped));
+            next = if flipped { node.right.as_deref() } else { node.left.as_deref() }
+        }
+    }
+}
+
+impl<'a, T> Iterator for Nodes<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        let (node, flipped) = self.stack.pop()?;
+        self.descend(if flipped { node.left.as_deref() } else { node.right.as_deref() }, flipped);
+        self.remaining -= 1;
+        Some(&node.item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> Clone for Nodes<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { stack: self.stack.clone(), remaining: self.remaining }
+    }
+}
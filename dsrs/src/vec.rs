@@ -0,0 +1,701 @@
+//! Growable array with a configurable growth policy.
+use mem::alloc::{grow_with, shrink_with, Layout};
+use mem::ptr::NonNull;
+
+use std::alloc::{handle_alloc_error, AllocErr, AllocRef, Global};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, Result};
+use std::iter::{FromIterator, FusedIterator};
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
+use std::{ptr, slice};
+
+/// When a [`Vector`] grows or shrinks its capacity.
+///
+/// Both rates are percentages of the current capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrowthPolicy {
+    growth: usize,
+    shrink: usize
+}
+
+impl GrowthPolicy {
+    /// Doubles when full, halves when only a quarter is used.
+    pub const DOUBLE: Self = Self::new(200, 25);
+
+    /// A full vector grows to `growth` percent of its capacity, and after
+    /// a removal leaves at most `shrink` percent of it in use, the capacity
+    /// is reduced by the same factor. A zero `shrink` never releases memory.
+    ///
+    /// # Panics
+    ///
+    /// If `growth` is not above 100, or if shrinking could leave the vector
+    /// full, which happens when `growth * shrink` is not below 100².
+    #[must_use]
+    #[inline]
+    pub const fn new(growth: usize, shrink: usize) -> Self {
+        assert!(growth > 100, "the growth rate must be above 100%");
+        assert!(shrink < 10_000 / growth, "shrinking must not leave the vector full");
+        Self { growth, shrink }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn growth(&self) -> usize {
+        self.growth
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn shrink(&self) -> usize {
+        self.shrink
+    }
+
+    /// New capacity to hold at least `required` items.
+    #[inline]
+    fn grown(self, capacity: usize, required: usize) -> usize {
+        (capacity.saturating_mul(self.growth) / 100).max(required)
+    }
+
+    /// Reduced capacity for `length` items, if it should shrink.
+    #[inline]
+    fn shrunk(self, capacity: usize, length: usize) -> Option<usize> {
+        let threshold = capacity.saturating_mul(self.shrink) / 100;
+        if self.shrink > 0 && length <= threshold && capacity > 0 {
+            Some((capacity.saturating_mul(100) / self.growth).max(length))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for GrowthPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::DOUBLE
+    }
+}
+
+/// Contiguous growable array, allocated with `A`.
+///
+/// Works like `Vec`, but the growth and shrinking rates come from a
+/// [`GrowthPolicy`], and allocation failures can be handled with
+/// [`try_reserve`](Vector::try_reserve).
+pub struct Vector<T, A: AllocRef = Global> {
+    ptr: NonNull<T>,
+    capacity: usize,
+    length: usize,
+    policy: GrowthPolicy,
+    alloc: A,
+    marker: PhantomData<T>
+}
+
+// SAFETY: the vector owns its items, just like a 'Vec'
+unsafe impl<T: Send, A: AllocRef + Send> Send for Vector<T, A> {}
+// SAFETY: shared references only give shared access to the items
+unsafe impl<T: Sync, A: AllocRef + Sync> Sync for Vector<T, A> {}
+
+impl<T> Vector<T> {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn with_policy(policy: GrowthPolicy) -> Self {
+        Self::with_policy_in(policy, Global)
+    }
+}
+
+impl<T, A: AllocRef> Vector<T, A> {
+    #[must_use]
+    #[inline]
+    pub const fn new_in(alloc: A) -> Self {
+        Self::with_policy_in(GrowthPolicy::DOUBLE, alloc)
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn with_policy_in(policy: GrowthPolicy, alloc: A) -> Self {
+        // zero-sized items never need memory
+        let capacity = if size_of::<T>() == 0 { usize::MAX } else { 0 };
+        Self { ptr: NonNull::dangling(), capacity, length: 0, policy, alloc, marker: PhantomData }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn policy(&self) -> GrowthPolicy {
+        self.policy
+    }
+
+    /// Changes the policy for the next reallocations.
+    #[inline]
+    pub fn set_policy(&mut self, policy: GrowthPolicy) {
+        self.policy = policy
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first 'length' items are initialized
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.length) }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first 'length' items are initialized
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.length) }
+    }
+
+    /// Moves the items to a block with room for `capacity` items.
+    ///
+    /// # Safety
+    ///
+    /// `capacity` can't be less than the length, and `T` can't be
+    /// zero-sized.
+    #[inline]
+    unsafe fn reallocate(&mut self, capacity: usize) -> std::result::Result<(), AllocErr> {
+        debug_assert!(capacity >= self.length && size_of::<T>() > 0);
+        let new_layout = Layout::array::<T>(capacity).map_err(|_| AllocErr)?;
+        let old_size = self.capacity * size_of::<T>();
+        // SAFETY: the current capacity was already checked
+        let old_layout = unsafe { Layout::from_size_align_unchecked(old_size, new_layout.align()) };
+
+        self.ptr = if self.capacity == 0 {
+            NonNull::from_inner(self.alloc.alloc(new_layout.inner())?.cast())
+        } else if capacity == 0 {
+            // SAFETY: the block was allocated with this layout, and is empty
+            unsafe { self.alloc.dealloc(self.ptr.cast().inner(), old_layout.inner()) };
+            NonNull::dangling()
+        } else if capacity > self.capacity {
+            // SAFETY: the block was allocated with 'alloc' and 'old_layout'
+            unsafe { grow_with(self.ptr, old_layout, new_layout, &mut self.alloc) }?
+        } else {
+            // SAFETY: same as above, and no items are lost
+            unsafe { shrink_with(self.ptr, old_layout, new_layout, &mut self.alloc) }?
+        };
+        self.capacity = capacity;
+        Ok(())
+    }
+
+    /// Makes room for at least `additional` more items, following the
+    /// growth policy.
+    ///
+    /// # Errors
+    ///
+    /// When the allocator fails or the capacity would overflow, leaving the
+    /// vector unchanged.
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> std::result::Result<(), AllocErr> {
+        let required = self.length.checked_add(additional).ok_or(AllocErr)?;
+        if required <= self.capacity {
+            return Ok(())
+        }
+        let capacity = self.policy.grown(self.capacity, required);
+        // SAFETY: 'capacity' is above the length, and zero-sized items
+        // never get here, as their capacity is maximum
+        unsafe { self.reallocate(capacity) }
+    }
+
+    /// Same as [`try_reserve`](Vector::try_reserve), but aborts on failures.
+    ///
+    /// # Panics
+    ///
+    /// When the capacity would overflow.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        if self.try_reserve(additional).is_err() {
+            let size = self.length.saturating_add(additional).saturating_mul(size_of::<T>());
+            let layout = Layout::from_size_align(size, Layout::new::<T>().align())
+                .unwrap_or_else(|_| panic!("capacity overflow"));
+            handle_alloc_error(layout.inner())
+        }
+    }
+
+    /// Releases memory according to the shrinking policy.
+    #[inline]
+    fn shrink_by_policy(&mut self) {
+        if size_of::<T>() == 0 {
+            return
+        }
+        if let Some(capacity) = self.policy.shrunk(self.capacity, self.length) {
+            // SAFETY: 'capacity' is not less than the length, and a
+            // failure just keeps the old block
+            let _ = unsafe { self.reallocate(capacity) };
+        }
+    }
+
+    /// Reduces the capacity to the length.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        if size_of::<T>() > 0 && self.capacity > self.length {
+            // SAFETY: same as above
+            let _ = unsafe { self.reallocate(self.length) };
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.reserve(1);
+        // SAFETY: there is room for one more item
+        unsafe { self.ptr.as_ptr().add(self.length).write(value) };
+        self.length += 1
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None
+        }
+        self.length -= 1;
+        // SAFETY: the item was initialized and is now out of the vector
+        let value = unsafe { self.ptr.as_ptr().add(self.length).read() };
+        self.shrink_by_policy();
+        Some(value)
+    }
+
+    /// Inserts `value` at `index`, shifting the next items.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length.
+    #[inline]
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.length, "insertion index out of bounds");
+        self.reserve(1);
+        // SAFETY: there is room for one more item, and 'index' is valid
+        unsafe {
+            let slot = self.ptr.as_ptr().add(index);
+            ptr::copy(slot, slot.add(1), self.length - index);
+            slot.write(value)
+        }
+        self.length += 1
+    }
+
+    /// Removes the item at `index`, shifting the next items.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.length, "removal index out of bounds");
+        self.length -= 1;
+        // SAFETY: 'index' holds an initialized item, which is moved out
+        // before the following ones are shifted over it
+        let value = unsafe {
+            let slot = self.ptr.as_ptr().add(index);
+            let value = slot.read();
+            ptr::copy(slot.add(1), slot, self.length - index);
+            value
+        };
+        self.shrink_by_policy();
+        value
+    }
+
+    /// Drops the items after the first `length`.
+    #[inline]
+    pub fn truncate(&mut self, length: usize) {
+        if length >= self.length {
+            return
+        }
+        let tail: *mut [T] = &mut self.as_mut_slice()[length..];
+        // the length is updated first, in case of panics
+        self.length = length;
+        // SAFETY: the items are out of the vector and dropped only once
+        unsafe { ptr::drop_in_place(tail) };
+        self.shrink_by_policy()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Binary search in a vector sorted by `compare`.
+    ///
+    /// Unlike the slice method, with many matches this always returns the
+    /// first of them. Otherwise, `Err` has the position where a matching
+    /// item could be inserted.
+    ///
+    /// # Errors
+    ///
+    /// When no item matches.
+    #[inline]
+    pub fn binary_search_by<F>(&self, mut compare: F) -> std::result::Result<usize, usize>
+    where F: FnMut(&T) -> Ordering {
+        let index = self.partition_point(|item| compare(item) == Ordering::Less);
+        match self.get(index).map(compare) {
+            Some(Ordering::Equal) => Ok(index),
+            _ => Err(index)
+        }
+    }
+
+    /// Index of the first item where `pred` is false, in a vector
+    /// partitioned by it.
+    #[must_use]
+    #[inline]
+    pub fn partition_point<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        let (mut start, mut end) = (0, self.length);
+        while start < end {
+            let middle = start + (end - start) / 2;
+            if pred(&self[middle]) {
+                start = middle + 1
+            } else {
+                end = middle
+            }
+        }
+        start
+    }
+
+    /// Inserts `value` in a vector sorted by `compare`, after any equal
+    /// items, and returns its position.
+    #[inline]
+    pub fn insert_sorted_by<F>(&mut self, value: T, mut compare: F) -> usize
+    where F: FnMut(&T, &T) -> Ordering {
+        let index = self.partition_point(|item| compare(item, &value) != Ordering::Greater);
+        self.insert(index, value);
+        index
+    }
+}
+
+impl<T: Ord, A: AllocRef> Vector<T, A> {
+    /// Same as [`insert_sorted_by`](Vector::insert_sorted_by), with the
+    /// natural order.
+    #[inline]
+    pub fn insert_sorted(&mut self, value: T) -> usize {
+        self.insert_sorted_by(value, Ord::cmp)
+    }
+}
+
+impl<T, A: AllocRef> Drop for Vector<T, A> {
+    #[inline]
+    fn drop(&mut self) {
+        let items: *mut [T] = self.as_mut_slice();
+        // SAFETY: every item is dropped once, and the block is released
+        // with the layout it was allocated with
+        unsafe {
+            ptr::drop_in_place(items);
+            if size_of::<T>() > 0 && self.capacity > 0 {
+                let size = self.capacity * size_of::<T>();
+                let layout = Layout::from_size_align_unchecked(size, Layout::new::<T>().align());
+                self.alloc.dealloc(self.ptr.cast().inner(), layout.inner())
+            }
+        }
+    }
+}
+
+impl<T, A: AllocRef> Deref for Vector<T, A> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, A: AllocRef> DerefMut for Vector<T, A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> Default for Vector<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, A: AllocRef + Clone> Clone for Vector<T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut vector = Self::with_policy_in(self.policy, self.alloc.clone());
+        vector.extend(self.iter().cloned());
+        vector
+    }
+}
+
+impl<T: Debug, A: AllocRef> Debug for Vector<T, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, A: AllocRef> PartialEq for Vector<T, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, A: AllocRef> Eq for Vector<T, A> {}
+
+impl<T> FromIterator<T> for Vector<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = Self::new();
+        vector.extend(iter);
+        vector
+    }
+}
+
+impl<T, A: AllocRef> Extend<T> for Vector<T, A> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value)
+        }
+    }
+}
+
+/// Owning iterator over the items of a [`Vector`].
+///
+/// Created by [`Vector::into_iter`](IntoIterator::into_iter).
+pub struct IntoIter<T, A: AllocRef = Global> {
+    vector: Vector<T, A>,
+    // items before this were already moved out
+    start: usize
+}
+
+impl<T, A: AllocRef> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.vector.length {
+            return None
+        }
+        self.start += 1;
+        // SAFETY: the item is initialized and never read again
+        Some(unsafe { self.vector.ptr.as_ptr().add(self.start - 1).read() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.vector.length - self.start;
+        (length, Some(length))
+    }
+}
+
+impl<T, A: AllocRef> DoubleEndedIterator for IntoIter<T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.vector.length {
+            return None
+        }
+        self.vector.length -= 1;
+        // SAFETY: the item is initialized and now out of the vector
+        Some(unsafe { self.vector.ptr.as_ptr().add(self.vector.length).read() })
+    }
+}
+
+impl<T, A: AllocRef> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: AllocRef> FusedIterator for IntoIter<T, A> {}
+
+impl<T, A: AllocRef> Drop for IntoIter<T, A> {
+    #[inline]
+    fn drop(&mut self) {
+        let remaining: *mut [T] = &mut self.vector.as_mut_slice()[self.start..];
+        // only the memory is left for the vector to release
+        self.vector.length = 0;
+        // SAFETY: the remaining items were not moved out yet
+        unsafe { ptr::drop_in_place(remaining) }
+    }
+}
+
+impl<T, A: AllocRef> IntoIterator for Vector<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T, A> {
+        IntoIter { vector: self, start: 0 }
+    }
+}
+
+impl<'a, T, A: AllocRef> IntoIterator for &'a Vector<T, A> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: AllocRef> IntoIterator for &'a mut Vector<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::Layout as RawLayout;
+    use std::cell::Cell;
+    use std::ptr::NonNull as RawNonNull;
+    use std::rc::Rc;
+
+    /// Global allocator with a limit on the total of live bytes.
+    #[derive(Clone)]
+    struct Limited {
+        used: Rc<Cell<usize>>,
+        limit: usize
+    }
+
+    impl Limited {
+        fn new(limit: usize) -> Self {
+            Self { used: Rc::default(), limit }
+        }
+    }
+
+    // SAFETY: every block comes from 'Global'
+    unsafe impl AllocRef for Limited {
+        fn alloc(&mut self, layout: RawLayout) -> std::result::Result<RawNonNull<[u8]>, AllocErr> {
+            let used = self.used.get() + layout.size();
+            if used > self.limit {
+                return Err(AllocErr)
+            }
+            self.used.set(used);
+            Global.alloc(layout)
+        }
+
+        unsafe fn dealloc(&mut self, ptr: RawNonNull<u8>, layout: RawLayout) {
+            self.used.set(self.used.get() - layout.size());
+            // SAFETY: guaranteed by the caller
+            unsafe { Global.dealloc(ptr, layout) }
+        }
+    }
+
+    #[test]
+    fn grows_and_shrinks_by_policy() {
+        let mut vector = Vector::new();
+        let mut capacities = Vec::new();
+        for i in 0..9 {
+            vector.push(i);
+            capacities.push(vector.capacity())
+        }
+        assert_eq!(capacities, vec![1, 2, 4, 4, 8, 8, 8, 8, 16]);
+
+        while vector.len() > 2 {
+            vector.pop();
+        }
+        assert_eq!(vector.capacity(), 4);
+        assert_eq!(vector.pop(), Some(1));
+        assert_eq!((vector.as_slice(), vector.capacity()), (&[0][..], 2));
+
+        let mut vector = Vector::with_policy(GrowthPolicy::new(150, 0));
+        vector.extend(0..10);
+        vector.clear();
+        assert!(vector.capacity() >= 10);
+        vector.shrink_to_fit();
+        assert_eq!(vector.capacity(), 0)
+    }
+
+    #[test]
+    #[should_panic(expected = "shrinking must not leave the vector full")]
+    fn invalid_policy() {
+        let _ = GrowthPolicy::new(200, 50);
+    }
+
+    #[test]
+    fn insert_remove_and_search() {
+        let mut orders: Vector<(u32, &str)> = Vector::new();
+        for &(price, id) in &[(30, "a"), (10, "b"), (30, "c"), (20, "d"), (10, "e")] {
+            orders.insert_sorted_by((price, id), |x, y| x.0.cmp(&y.0));
+        }
+        assert_eq!(orders.iter().map(|order| order.1).collect::<String>(), "bedac");
+
+        assert_eq!(orders.binary_search_by(|order| order.0.cmp(&30)), Ok(3));
+        assert_eq!(orders.binary_search_by(|order| order.0.cmp(&10)), Ok(0));
+        assert_eq!(orders.binary_search_by(|order| order.0.cmp(&25)), Err(3));
+
+        assert_eq!(orders.remove(3), (30, "a"));
+        orders.insert(0, (0, "f"));
+        assert_eq!(orders.iter().map(|order| order.1).collect::<String>(), "fbedc");
+        assert_eq!(orders.insert_sorted((20, "g")), 4)
+    }
+
+    #[test]
+    fn try_reserve_reports_failures() {
+        // growing needs both blocks at once
+        let alloc = Limited::new(96);
+        let mut vector = Vector::<u64, _>::new_in(alloc.clone());
+        assert_eq!(vector.try_reserve(4), Ok(()));
+        vector.extend(0..8);
+        assert_eq!(alloc.used.get(), 64);
+
+        assert_eq!(vector.try_reserve(1), Err(AllocErr));
+        assert_eq!(vector.try_reserve(usize::MAX), Err(AllocErr));
+        assert_eq!((vector.len(), vector.capacity()), (8, 8));
+        assert_eq!(vector.iter().sum::<u64>(), 28);
+
+        drop(vector);
+        assert_eq!(alloc.used.get(), 0)
+    }
+
+    #[test]
+    fn zero_sized_items() {
+        let mut vector = Vector::new_in(Limited::new(0));
+        for _ in 0..100 {
+            vector.push(())
+        }
+        assert_eq!((vector.remove(50), vector.len()), ((), 99));
+        assert_eq!(vector.into_iter().count(), 99)
+    }
+
+    #[test]
+    fn into_iter_drops_the_rest() {
+        let value = Rc::new(());
+        let vector: Vector<_> = (0..10).map(|_| Rc::clone(&value)).collect();
+        let mut iter = vector.clone().into_iter();
+        assert!(iter.next().is_some() && iter.next_back().is_some());
+        assert_eq!(iter.len(), 8);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 11);
+
+        let mut vector = vector;
+        vector.truncate(3);
+        assert_eq!(Rc::strong_count(&value), 4);
+        assert_eq!(format!("{:?}", vector), "[(), (), ()]")
+    }
+}